@@ -1,13 +1,52 @@
-use crate::db::{Database, Post, PostFilter};
+use crate::config::{self, Config, LimitsConfig, MarkReadMode};
+use crate::db::{lock_db, Database, Feed, Post, PostFilter};
+use crate::diagnostics;
+use crate::export::{self, ExportFormat};
 use crate::input::TextInput;
+use crate::keymap::KeyMap;
+use crate::metrics::DebugMetrics;
 use crate::navigation::{FocusPane, NavNode, SidebarState, SmartView};
+use crate::opml::{self, OpmlFeed};
+use crate::reading;
+use crate::rss;
+use crate::stats::AppStats;
+use crate::theme::{ColorMode, ThemeVariant};
+use ratatui::layout::Rect;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
 
+/// Read Later saves older than this count as a neglected backlog
+pub const STALE_READ_LATER_DAYS: i64 = 14;
+
+/// Feeds that have failed this many fetches in a row are flagged as broken
+pub const FAILING_FEED_STREAK: u32 = 3;
+
+/// Deletions kept for undo
+const UNDO_DEPTH: usize = 10;
+
+/// `user_preferences` key of the view or category open when the app last ran
+const LAST_NODE_PREFERENCE: &str = "last_node";
+/// `user_preferences` key of the `u` show-read toggle
+const SHOW_READ_PREFERENCE: &str = "show_read";
+
+/// A deletion captured before it happened, so it can be undone
+#[derive(Debug, Clone)]
+pub enum Deleted {
+    Post(Post),
+    Feed { feed: Feed, posts: Vec<Post> },
+}
+
+/// Shorten `s` to at most `max_len` characters, ending in `...` when cut.
+/// Counts chars rather than bytes so titles in any script cut cleanly.
 fn truncate_str(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
+    if s.chars().count() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+        let kept: String = s.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", kept)
     }
 }
 
@@ -18,72 +57,349 @@ pub enum InputMode {
     AddingFeed,
     AddingCategory,
     SelectingCategory,
+    /// Picking one of several feeds found on a pasted web page
+    ChoosingDiscoveredFeed,
     Confirming(ConfirmAction),
     Help,
+    Dashboard,
+    Searching,
+    /// Typing an in-list filter; the posts list narrows as the text changes
+    Filtering,
     EditingCategoryFeeds(String),
+    MovingCategoryFeeds(String),
+    /// Typing the path of an OPML file to import; holds the feed manager
+    /// category it was opened from, `None` for the welcome screen
+    ImportingOpml(Option<String>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::enum_variant_names)]
 pub enum ConfirmAction {
     DeletePost(i64),
     #[allow(dead_code)]
     DeleteFeed(i64),
     DeleteCategory(String),
+    DeleteCategoryPosts(String),
+    /// Re-adding an existing feed: offer to move it from its current category
+    MoveFeed { feed_id: i64, from: String, to: String },
+    /// The pasted URL was a web page; offer the feed it links to instead
+    UseDiscoveredFeed { url: String, title: Option<String> },
+}
+
+/// Ordering of the feed list in the feed manager
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FeedSort {
+    Alphabetical,
+    RecentlyUpdated,
+    MostUnread,
+}
+
+impl FeedSort {
+    pub fn next(self) -> Self {
+        match self {
+            FeedSort::Alphabetical => FeedSort::RecentlyUpdated,
+            FeedSort::RecentlyUpdated => FeedSort::MostUnread,
+            FeedSort::MostUnread => FeedSort::Alphabetical,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FeedSort::Alphabetical => "A-Z",
+            FeedSort::RecentlyUpdated => "Recently updated",
+            FeedSort::MostUnread => "Most unread",
+        }
+    }
+}
+
+/// Quick narrowing applied on top of a smart view without leaving it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SubFilter {
+    #[default]
+    All,
+    Unread,
+    Today,
+}
+
+impl SubFilter {
+    pub fn next(self) -> Self {
+        match self {
+            SubFilter::All => SubFilter::Unread,
+            SubFilter::Unread => SubFilter::Today,
+            SubFilter::Today => SubFilter::All,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SubFilter::All => "all",
+            SubFilter::Unread => "unread",
+            SubFilter::Today => "today",
+        }
+    }
+
+    fn matches(&self, post: &Post) -> bool {
+        match self {
+            SubFilter::All => true,
+            SubFilter::Unread => !post.is_read,
+            SubFilter::Today => post.pub_date.is_some_and(|date| {
+                date.with_timezone(&chrono::Local).date_naive() == chrono::Local::now().date_naive()
+            }),
+        }
+    }
+}
+
+/// Ordering of the posts list, applied in memory after loading
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortMode {
+    #[default]
+    DateDesc,
+    DateAsc,
+    TitleAsc,
+    FeedName,
+    UnreadFirst,
+}
+
+impl SortMode {
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::DateDesc => SortMode::DateAsc,
+            SortMode::DateAsc => SortMode::TitleAsc,
+            SortMode::TitleAsc => SortMode::FeedName,
+            SortMode::FeedName => SortMode::UnreadFirst,
+            SortMode::UnreadFirst => SortMode::DateDesc,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortMode::DateDesc => "newest",
+            SortMode::DateAsc => "oldest",
+            SortMode::TitleAsc => "title",
+            SortMode::FeedName => "feed",
+            SortMode::UnreadFirst => "unread first",
+        }
+    }
+
+    /// Sort `posts`, newest first within ties. Undated posts always go last.
+    fn sort(&self, posts: &mut [Post]) {
+        let newest_first = |a: &Post, b: &Post| match (a.pub_date, b.pub_date) {
+            (Some(a), Some(b)) => b.cmp(&a),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        };
+        match self {
+            SortMode::DateDesc => posts.sort_by(newest_first),
+            SortMode::DateAsc => posts.sort_by(|a, b| match (a.pub_date, b.pub_date) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                _ => newest_first(a, b),
+            }),
+            SortMode::TitleAsc => posts.sort_by(|a, b| {
+                a.title.to_lowercase().cmp(&b.title.to_lowercase()).then_with(|| newest_first(a, b))
+            }),
+            SortMode::FeedName => posts.sort_by(|a, b| {
+                let feed = |p: &Post| p.feed_title.as_deref().unwrap_or_default().to_lowercase();
+                feed(a).cmp(&feed(b)).then_with(|| newest_first(a, b))
+            }),
+            SortMode::UnreadFirst => {
+                posts.sort_by(|a, b| a.is_read.cmp(&b.is_read).then_with(|| newest_first(a, b)))
+            }
+        }
+    }
+}
+
+/// Result of validating a URL typed into the add-feed prompt
+#[derive(Debug, Clone)]
+pub enum FeedCheck {
+    Valid { url: String, title: Option<String> },
+    /// The URL was a web page; these feeds were found from it and validated
+    Discovered(Vec<DiscoveredFeed>),
+    Invalid { url: String, error: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct DiscoveredFeed {
+    pub url: String,
+    pub title: Option<String>,
+}
+
+/// Teleprompter-style scrolling of the open article
+#[derive(Debug, Clone, Default)]
+pub struct AutoScroll {
+    pub paused: bool,
+    /// Fractional lines accumulated between ticks
+    carry: f64,
 }
 
 pub struct App {
     pub db: Arc<Mutex<Database>>,
+    pub config: Config,
     pub posts: Vec<Post>,
     pub focus: FocusPane,
     pub sidebar: SidebarState,
     pub active_node: NavNode,
     pub selected_index: usize,
     pub scroll_offset: u16,
+    /// Horizontal scroll of the article view, used when wrapping is off
+    pub h_scroll_offset: u16,
+    pub article_wrap: bool,
+    /// Wrapped line count and visible height of the open article, updated on draw
+    pub article_lines: u16,
+    pub article_view_height: u16,
+    /// Where the sidebar items and posts list were last drawn, and the posts
+    /// list's scroll offset, for mapping mouse clicks to items
+    pub sidebar_area: Rect,
+    pub posts_area: Rect,
+    pub posts_offset: usize,
+    /// Article view shows the feed summary rather than the full content
+    pub show_summary: bool,
+    /// Dashboard shows the ASCII banner; starts from `ui.show_ascii_banner`, `b` toggles it
+    pub show_banner: bool,
+    pub auto_scroll: Option<AutoScroll>,
+    /// Posts the selection has moved past in a list, kept for this session only
+    pub seen_posts: HashSet<i64>,
+    /// Most recent deletions, newest last
+    pub undo_stack: Vec<Deleted>,
+    /// Rows fetched for the active view so far, before in-memory filtering
+    posts_loaded: usize,
+    /// The last page came back full, so scrolling to the end loads another
+    pub more_posts: bool,
     pub exit: bool,
     pub message: Option<String>,
     pub is_loading: bool,
+    pub spinner_frame: usize,
+    /// `(done, total)` while a background OPML import is running
+    pub import_progress: Option<(usize, usize)>,
+    /// Why the typed OPML path couldn't be imported, shown in the prompt
+    pub import_error: Option<String>,
     pub input_mode: InputMode,
     pub text_input: TextInput,
     pub feeds: Vec<crate::db::Feed>,
     pub selected_feed_index: usize,
     pub show_read: bool,
     pub pending_feed_url: Option<String>,
+    /// Title read from the pending feed while validating it
+    pub pending_feed_title: Option<String>,
+    pub discovered_feeds: Vec<DiscoveredFeed>,
+    pub discovered_feed_index: usize,
     pub category_feeds: Vec<crate::db::Feed>,
+    /// Unread posts per feed id for the feed manager
+    pub category_feed_unread: HashMap<i64, usize>,
     pub category_feed_index: usize,
+    pub feed_sort: FeedSort,
+    pub sub_filter: SubFilter,
+    pub sort_mode: SortMode,
+    /// Active search; while set, the posts list shows matches instead of the active node
+    pub search_term: Option<String>,
+    /// In-list title filter, with the full list it narrows kept for restoring
+    pub list_filter: Option<String>,
+    filter_base: Option<Vec<Post>>,
+    pub data_dir: PathBuf,
+    pub config_path: PathBuf,
+    /// The config file didn't parse and `config` was salvaged from it, so it
+    /// must not be written back
+    pub config_recovered: bool,
+    pub db_path: PathBuf,
+    pub unhealthy_feeds: usize,
+    pub stale_read_later: usize,
+    pub debug: Option<DebugMetrics>,
+    pub color_mode: ColorMode,
+    pub theme: ThemeVariant,
+    /// Key bindings for the main panes, from `[keybindings]`
+    pub keymap: KeyMap,
+    /// Freshly fetched web page text, kept beside the saved copy until the
+    /// user chooses to save it
+    pub live_pages: HashMap<i64, String>,
+    /// Articles show their entry in `live_pages` rather than the saved text
+    pub show_live_page: bool,
+    /// Shared by article full-text fetches so only a few run at once
+    pub fulltext_permits: Arc<Semaphore>,
+    /// Posts whose web page is being fetched for full text
+    pub fulltext_loading: HashSet<i64>,
+    /// Posts showing text fetched from their web page this session
+    pub fulltext_loaded: HashSet<i64>,
+    pub stats: AppStats,
+    /// System clipboard, opened on first copy
+    clipboard: Option<arboard::Clipboard>,
 }
 
 impl App {
-    pub fn new(db: Database) -> Self {
+    pub fn new(db: Database, config: Config) -> Self {
         let db_arc = Arc::new(Mutex::new(db));
-        let feeds = db_arc.lock().unwrap().get_feeds().unwrap_or_default();
+        let feeds = lock_db(&db_arc).get_feeds().unwrap_or_default();
 
         let mut sidebar = SidebarState::new();
-        {
-            let db = db_arc.lock().unwrap();
+        sidebar.all_feeds_per_feed = config.limits.all_feeds_per_feed;
+        let (unhealthy_feeds, stale_read_later, last_node, show_read) = {
+            let db = lock_db(&db_arc);
             sidebar.load_categories(&db);
             sidebar.update_counts(&db);
-        }
+            (
+                db.get_unhealthy_feeds_count().unwrap_or(0),
+                db.get_stale_read_later_count(STALE_READ_LATER_DAYS).unwrap_or(0),
+                db.get_preference(LAST_NODE_PREFERENCE).ok().flatten(),
+                db.get_preference_as(SHOW_READ_PREFERENCE).ok().flatten().unwrap_or(false),
+            )
+        };
 
         let is_first_run = feeds.is_empty();
-        let active_node = NavNode::SmartView(SmartView::Fresh);
+        let theme = ThemeVariant::from_str(&config.app.theme);
+        // Open the configured start view, else reopen where the last session
+        // left off, unless that category is gone
+        let active_node = config
+            .ui
+            .default_tab
+            .as_deref()
+            .and_then(SmartView::from_key)
+            .map(NavNode::SmartView)
+            .or_else(|| last_node.as_deref().and_then(NavNode::from_preference))
+            .filter(|node| sidebar.select_node(node))
+            .unwrap_or(NavNode::SmartView(SmartView::Fresh));
 
-        let posts = if !is_first_run {
-            db_arc.lock().unwrap().get_fresh_feed(10).unwrap_or_default()
+        let fresh = NavNode::SmartView(SmartView::Fresh);
+        let posts = if !is_first_run && active_node == fresh && !show_read {
+            lock_db(&db_arc)
+                .get_fresh_feed(config.limits.fresh_per_category)
+                .unwrap_or_default()
         } else {
             vec![]
         };
 
-        App {
+        let show_summary = config.ui.prefer_summary;
+        let show_banner = config.ui.show_ascii_banner;
+        let keymap = KeyMap::new(&config.keybindings.actions);
+        let fulltext_permits = Arc::new(Semaphore::new(config.feeds.max_fulltext_fetches.max(1)));
+
+        let mut app = App {
             db: db_arc,
+            config,
             posts,
             focus: FocusPane::Sidebar,
             sidebar,
             active_node,
             selected_index: 0,
             scroll_offset: 0,
+            h_scroll_offset: 0,
+            auto_scroll: None,
+            seen_posts: HashSet::new(),
+            undo_stack: Vec::new(),
+            posts_loaded: 0,
+            more_posts: false,
+            article_wrap: true,
+            article_lines: 0,
+            article_view_height: 0,
+            sidebar_area: Rect::default(),
+            posts_area: Rect::default(),
+            posts_offset: 0,
+            show_summary,
+            show_banner,
             exit: false,
             message: None,
             is_loading: !is_first_run,
+            spinner_frame: 0,
+            import_progress: None,
+            import_error: None,
             input_mode: if is_first_run {
                 InputMode::Welcome
             } else {
@@ -92,21 +408,79 @@ impl App {
             text_input: TextInput::new(),
             feeds,
             selected_feed_index: 0,
-            show_read: false,
+            show_read,
             pending_feed_url: None,
+            pending_feed_title: None,
+            discovered_feeds: Vec::new(),
+            discovered_feed_index: 0,
             category_feeds: vec![],
+            category_feed_unread: HashMap::new(),
             category_feed_index: 0,
+            feed_sort: FeedSort::Alphabetical,
+            sub_filter: SubFilter::All,
+            sort_mode: SortMode::default(),
+            search_term: None,
+            list_filter: None,
+            filter_base: None,
+            data_dir: PathBuf::from("."),
+            config_path: PathBuf::new(),
+            config_recovered: false,
+            db_path: PathBuf::new(),
+            unhealthy_feeds,
+            stale_read_later,
+            debug: None,
+            color_mode: ColorMode::TrueColor,
+            theme,
+            keymap,
+            live_pages: HashMap::new(),
+            show_live_page: false,
+            fulltext_permits,
+            fulltext_loading: HashSet::new(),
+            fulltext_loaded: HashSet::new(),
+            stats: AppStats::default(),
+            clipboard: None,
+        };
+        if !is_first_run && (app.active_node != fresh || show_read) {
+            app.reload_posts_for_active_node();
         }
+        app
     }
 
     pub fn load_category_feeds(&mut self, category: &str) {
-        self.category_feeds = self
-            .db
-            .lock()
-            .unwrap()
-            .get_feeds_by_category(category)
-            .unwrap_or_default();
+        {
+            let db = lock_db(&self.db);
+            self.category_feeds = db.get_feeds_by_category(category).unwrap_or_default();
+            self.category_feed_unread = self
+                .category_feeds
+                .iter()
+                .map(|feed| (feed.id, db.get_unread_count_by_feed(feed.id).unwrap_or(0)))
+                .collect();
+        }
+        self.sort_category_feeds();
+        self.category_feed_index = 0;
+    }
+
+    pub fn cycle_feed_sort(&mut self) {
+        self.feed_sort = self.feed_sort.next();
+        self.sort_category_feeds();
         self.category_feed_index = 0;
+        self.message = Some(format!("Sorted feeds: {}", self.feed_sort.label()));
+    }
+
+    fn sort_category_feeds(&mut self) {
+        match self.feed_sort {
+            FeedSort::Alphabetical => self.category_feeds.sort_by_key(|f| {
+                f.title.clone().unwrap_or_else(|| f.url.clone()).to_lowercase()
+            }),
+            FeedSort::RecentlyUpdated => self
+                .category_feeds
+                .sort_by_key(|f| std::cmp::Reverse(f.last_fetched_at)),
+            FeedSort::MostUnread => {
+                let unread = &self.category_feed_unread;
+                self.category_feeds
+                    .sort_by_key(|f| std::cmp::Reverse(unread.get(&f.id).copied().unwrap_or(0)))
+            }
+        }
     }
 
     pub fn next_category_feed(&mut self) {
@@ -122,18 +496,84 @@ impl App {
     }
 
     pub fn delete_category_feed(&mut self) {
-        if let Some(feed) = self.category_feeds.get(self.category_feed_index) {
+        if let Some(feed) = self.category_feeds.get(self.category_feed_index).cloned() {
             let feed_id = feed.id;
             let feed_title = feed.title.clone().unwrap_or_else(|| feed.url.clone());
-            if self.db.lock().unwrap().delete_feed(feed_id).is_ok() {
+            let deleted = {
+                let db = lock_db(&self.db);
+                let posts = db.get_posts_by_feed(feed_id).unwrap_or_default();
+                db.delete_feed(feed_id).map(|_| posts)
+            };
+            if let Ok(posts) = deleted {
+                self.push_undo(Deleted::Feed { feed, posts });
                 self.category_feeds.remove(self.category_feed_index);
                 if self.category_feed_index >= self.category_feeds.len() && !self.category_feeds.is_empty() {
                     self.category_feed_index = self.category_feeds.len() - 1;
                 }
                 self.reload_feeds();
                 self.refresh_sidebar();
-                self.message = Some(format!("Deleted feed: {}", truncate_str(&feed_title, 30)));
+                self.message = Some(format!("Deleted feed: {} (U to undo)", truncate_str(&feed_title, 30)));
+            }
+        }
+    }
+
+    pub fn delete_post(&mut self, post_id: i64) {
+        let Some(post) = self.posts.iter().find(|p| p.id == post_id).cloned() else {
+            return;
+        };
+        if lock_db(&self.db).delete_post(post_id).is_ok() {
+            self.posts.retain(|p| p.id != post_id);
+            if self.selected_index >= self.posts.len() && !self.posts.is_empty() {
+                self.selected_index = self.posts.len() - 1;
+            }
+            self.refresh_sidebar();
+            self.message = Some("Post deleted (U to undo)".to_string());
+            self.push_undo(Deleted::Post(post));
+        }
+    }
+
+    fn push_undo(&mut self, deleted: Deleted) {
+        self.undo_stack.push(deleted);
+        if self.undo_stack.len() > UNDO_DEPTH {
+            self.undo_stack.remove(0);
+        }
+    }
+
+    /// Restore the most recent deletion
+    pub fn undo_delete(&mut self) {
+        let Some(deleted) = self.undo_stack.pop() else {
+            self.message = Some("Nothing to undo".to_string());
+            return;
+        };
+
+        let (result, title) = {
+            let db = lock_db(&self.db);
+            match &deleted {
+                Deleted::Post(post) => (db.restore_post(post), post.title.clone()),
+                Deleted::Feed { feed, posts } => (
+                    db.restore_feed(feed, posts),
+                    feed.title.clone().unwrap_or_else(|| feed.url.clone()),
+                ),
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                if let Deleted::Feed { feed, .. } = &deleted {
+                    self.reload_feeds();
+                    if let InputMode::EditingCategoryFeeds(category) = &self.input_mode
+                        && *category == feed.category
+                    {
+                        let category = category.clone();
+                        self.load_category_feeds(&category);
+                    }
+                }
+                self.refresh_sidebar();
+                self.reload_posts_for_active_node();
+                self.message = Some(format!("Restored: {}", truncate_str(&title, 40)));
             }
+            // Usually the same URL was added again since the delete
+            Err(e) => self.message = Some(format!("Couldn't restore {}: {}", truncate_str(&title, 30), e)),
         }
     }
 
@@ -148,89 +588,529 @@ impl App {
     pub fn focus_right(&mut self) {
         self.focus = match self.focus {
             FocusPane::Sidebar => FocusPane::Posts,
-            FocusPane::Posts => {
-                if !self.posts.is_empty() {
-                    FocusPane::Posts
-                } else {
-                    FocusPane::Posts
-                }
-            }
+            FocusPane::Posts => FocusPane::Posts,
             FocusPane::Article => FocusPane::Article,
         };
     }
 
     pub fn select_sidebar_item(&mut self) {
-        self.active_node = self.sidebar.selected_node();
+        let next_node = self.sidebar.selected_node();
+        if self.config.app.mark_read_on_category_switch && next_node != self.active_node {
+            self.mark_shown_category_posts_read();
+        }
+
+        if next_node != self.active_node {
+            self.sub_filter = SubFilter::All;
+        }
+        self.search_term = None;
+        if next_node != self.active_node {
+            let _ = lock_db(&self.db).set_preference(LAST_NODE_PREFERENCE, &next_node.to_preference());
+        }
+        self.active_node = next_node;
         self.reload_posts_for_active_node();
         self.selected_index = 0;
         self.focus = FocusPane::Posts;
     }
 
+    /// Jump straight to a smart view from anywhere, as if picked in the sidebar
+    pub fn open_smart_view(&mut self, view: SmartView) {
+        if !self.sidebar.select_node(&NavNode::SmartView(view)) {
+            return;
+        }
+        if self.focus == FocusPane::Article {
+            self.close_article();
+        }
+        self.select_sidebar_item();
+    }
+
+    /// Open the feed manager for the category being read, or else the one
+    /// selected in the sidebar
+    pub fn open_feed_manager(&mut self) {
+        let category = match &self.active_node {
+            NavNode::Category(cat) => Some(cat.clone()),
+            NavNode::SmartView(_) => self
+                .sidebar
+                .categories
+                .get(self.sidebar.category_index)
+                .or(self.sidebar.categories.first())
+                .cloned(),
+        };
+        match category {
+            Some(cat) => {
+                self.load_category_feeds(&cat);
+                self.input_mode = InputMode::EditingCategoryFeeds(cat);
+            }
+            None => self.message = Some("No categories yet; add a feed first".to_string()),
+        }
+    }
+
+    /// Handle a left click at terminal cell (`column`, `row`) in the two-pane
+    /// layout. Clicking a sidebar item opens it; clicking a post selects it,
+    /// and clicking the selected post opens it.
+    pub fn click(&mut self, column: u16, row: u16) {
+        let position = ratatui::layout::Position { x: column, y: row };
+        if self.sidebar_area.contains(position) {
+            if self.sidebar.select_row((row - self.sidebar_area.y) as usize) {
+                self.select_sidebar_item();
+            }
+        } else if self.posts_area.contains(position) {
+            // Skip the list's top border
+            let Some(line) = (row - self.posts_area.y).checked_sub(1) else {
+                return;
+            };
+            let index = self.posts_offset + line as usize;
+            if index >= self.posts.len() {
+                return;
+            }
+            if self.focus == FocusPane::Posts && index == self.selected_index {
+                self.open_article();
+            } else {
+                self.focus = FocusPane::Posts;
+                self.selected_index = index;
+            }
+        }
+    }
+
+    pub fn open_dashboard(&mut self) {
+        self.refresh_stats();
+        self.input_mode = InputMode::Dashboard;
+    }
+
+    /// Recompute the dashboard stats. Runs with every sidebar refresh, which
+    /// each post or feed change and each finished fetch goes through, so the
+    /// dashboard stays current even when a background refresh lands while
+    /// it's open.
+    pub fn refresh_stats(&mut self) {
+        self.stats = AppStats::from_db(&lock_db(&self.db)).unwrap_or_default();
+        self.stale_read_later = self.stats.stale_read_later_posts;
+    }
+
+    /// Move the selected Read Later post up (`-1`) or down (`1`) the list and persist the queue
+    pub fn move_read_later(&mut self, delta: isize) {
+        if self.active_node != NavNode::SmartView(SmartView::ReadLater) {
+            return;
+        }
+        if self.sub_filter != SubFilter::All {
+            self.message = Some("Clear the smart-view filter (f) to reorder the queue".to_string());
+            return;
+        }
+        let Some(target) = self.selected_index.checked_add_signed(delta) else {
+            return;
+        };
+        if target >= self.posts.len() {
+            return;
+        }
+
+        self.posts.swap(self.selected_index, target);
+        self.selected_index = target;
+
+        // Only the front of the queue may be loaded; the rest keeps its place behind it
+        let shown: Vec<i64> = self.posts.iter().map(|p| p.id).collect();
+        let db = lock_db(&self.db);
+        let hidden = read_later_queue(&db).into_iter().map(|p| p.id).filter(|id| !shown.contains(id));
+        let queue: Vec<i64> = if self.config.ui.read_later_oldest_first {
+            shown.iter().copied().chain(hidden).collect()
+        } else {
+            hidden.chain(shown.iter().rev().copied()).collect()
+        };
+        let saved = db.set_read_later_order(&queue).is_ok();
+        drop(db);
+        if saved {
+            self.reload_posts_for_active_node();
+        }
+    }
+
+    /// Replace the posts list with posts matching `term` across every feed
+    pub fn search(&mut self, term: &str) {
+        let term = term.trim();
+        if term.is_empty() {
+            return;
+        }
+        self.search_term = Some(term.to_string());
+        self.selected_index = 0;
+        self.reload_posts_for_active_node();
+        self.focus = FocusPane::Posts;
+    }
+
+    /// Ask for an OPML file to import. From the welcome screen the prompt starts
+    /// with the first OPML file found in the usual download spots.
+    pub fn start_opml_import(&mut self, origin: Option<String>) {
+        self.text_input.clear();
+        if origin.is_none() {
+            let home = std::env::var("HOME").unwrap_or_default();
+            let found = [
+                format!("{}/Downloads/feeds_organized.opml", home),
+                format!("{}/Downloads/feeds.opml", home),
+                format!("{}/feeds.opml", home),
+            ]
+            .into_iter()
+            .find(|path| std::path::Path::new(path).exists());
+            if let Some(path) = found {
+                self.text_input.insert_str(&path);
+            }
+        }
+        self.import_error = None;
+        self.input_mode = InputMode::ImportingOpml(origin);
+    }
+
+    /// Read and parse the OPML file named in the prompt. Problems are left in
+    /// `import_error` for the prompt to show, and `None` is returned.
+    pub fn read_opml_import(&mut self) -> Option<Vec<OpmlFeed>> {
+        let typed = self.text_input.value.trim();
+        if typed.is_empty() {
+            return None;
+        }
+        let path = config::expand_path(&PathBuf::from(typed));
+
+        let result = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Couldn't read {}: {}", path.display(), e))
+            .and_then(|content| {
+                if opml::is_opml(&content) {
+                    opml::parse(&content).map_err(|e| format!("{} isn't valid OPML: {}", path.display(), e))
+                } else {
+                    opml::parse_url_list(&content)
+                        .map_err(|e| format!("{} isn't OPML or a URL list: {}", path.display(), e))
+                }
+            });
+        match result {
+            Ok(feeds) if feeds.is_empty() => {
+                self.import_error = Some(format!("No feed outlines found in {}", path.display()));
+                None
+            }
+            Ok(feeds) => {
+                self.import_error = None;
+                Some(feeds)
+            }
+            Err(e) => {
+                self.import_error = Some(e);
+                None
+            }
+        }
+    }
+
+    /// Start narrowing the loaded posts by title, without querying the database
+    pub fn start_list_filter(&mut self) {
+        if self.filter_base.is_none() {
+            self.filter_base = Some(self.posts.clone());
+        }
+        self.text_input.clear();
+        if let Some(filter) = &self.list_filter {
+            self.text_input.insert_str(&filter.clone());
+        }
+        self.input_mode = InputMode::Filtering;
+    }
+
+    pub fn filter_active(&self) -> bool {
+        self.filter_base.is_some()
+    }
+
+    /// Re-filter the loaded posts by the text being typed
+    pub fn apply_list_filter(&mut self) {
+        self.sync_filter_base();
+        let Some(base) = self.filter_base.as_ref() else {
+            return;
+        };
+        let needle = self.text_input.value.to_lowercase();
+        self.posts = base
+            .iter()
+            .filter(|p| p.title.to_lowercase().contains(&needle))
+            .cloned()
+            .collect();
+        self.list_filter = (!needle.is_empty()).then(|| self.text_input.value.clone());
+        self.selected_index = 0;
+    }
+
+    /// Drop the in-list filter and show the full list again. Posts changed
+    /// while filtered keep their changes, and deleted ones stay gone.
+    pub fn clear_list_filter(&mut self) {
+        self.sync_filter_base();
+        let Some(base) = self.filter_base.take() else {
+            return;
+        };
+        self.list_filter = None;
+        let selected_id = self.posts.get(self.selected_index).map(|p| p.id);
+        self.posts = base;
+        self.selected_index = selected_id
+            .and_then(|id| self.posts.iter().position(|p| p.id == id))
+            .unwrap_or(0);
+    }
+
+    /// Fold the filtered posts back into the full list, so read, starred and
+    /// deleted posts aren't reverted when the filter changes
+    fn sync_filter_base(&mut self) {
+        let Some(base) = self.filter_base.take() else {
+            return;
+        };
+        let needle = self.list_filter.as_deref().unwrap_or_default().to_lowercase();
+        let mut shown: HashMap<i64, &Post> = self.posts.iter().map(|p| (p.id, p)).collect();
+        self.filter_base = Some(
+            base.into_iter()
+                .filter_map(|p| {
+                    if p.title.to_lowercase().contains(&needle) {
+                        shown.remove(&p.id).cloned()
+                    } else {
+                        Some(p)
+                    }
+                })
+                .collect(),
+        );
+    }
+
+    /// Drop the search results and go back to the active node's posts
+    pub fn clear_search(&mut self) {
+        if self.search_term.take().is_some() {
+            self.selected_index = 0;
+            self.reload_posts_for_active_node();
+        }
+    }
+
+    /// Step to the next post order (newest, oldest, title, feed, unread first)
+    /// and reload, starting from the top
+    pub fn cycle_sort_mode(&mut self) {
+        self.sort_mode = self.sort_mode.next();
+        self.selected_index = 0;
+        self.reload_posts_for_active_node();
+        self.message = Some(if self.active_node == NavNode::SmartView(SmartView::ReadLater) {
+            format!("Sort: {} (Read Later keeps its queue order)", self.sort_mode.label())
+        } else {
+            format!("Sort: {}", self.sort_mode.label())
+        });
+    }
+
+    /// Apply the sort mode, keeping the selected post selected. Read Later is
+    /// left in queue order.
+    fn sort_posts(&mut self) {
+        let is_queue = self.active_node == NavNode::SmartView(SmartView::ReadLater) && self.search_term.is_none();
+        if self.sort_mode == SortMode::DateDesc || is_queue {
+            return;
+        }
+        let selected_id = self.posts.get(self.selected_index).map(|p| p.id);
+        self.sort_mode.sort(&mut self.posts);
+        if let Some(id) = selected_id {
+            self.selected_index = self.posts.iter().position(|p| p.id == id).unwrap_or(0);
+        }
+    }
+
+    /// Step the smart-view sub-filter (all / unread / today) and reload in place
+    pub fn cycle_sub_filter(&mut self) {
+        if !matches!(self.active_node, NavNode::SmartView(_)) {
+            return;
+        }
+        self.sub_filter = self.sub_filter.next();
+        self.selected_index = 0;
+        self.reload_posts_for_active_node();
+    }
+
+    /// Mark everything currently listed for a category node as read ("glance and dismiss")
+    fn mark_shown_category_posts_read(&mut self) {
+        if !matches!(self.active_node, NavNode::Category(_)) {
+            return;
+        }
+
+        let ids: Vec<i64> = self.posts.iter().filter(|p| !p.is_read).map(|p| p.id).collect();
+        if !ids.is_empty() && lock_db(&self.db).mark_posts_read(&ids).is_ok() {
+            self.refresh_sidebar();
+        }
+    }
+
     pub fn reload_posts_for_active_node(&mut self) {
-        let db = self.db.lock().unwrap();
-        let posts = match &self.active_node {
+        self.list_filter = None;
+        self.filter_base = None;
+        let query_start = self.debug.as_ref().map(|_| Instant::now());
+        let db = lock_db(&self.db);
+        let (mut posts, page_size) = self.query_posts(&db, 0);
+        drop(db);
+
+        if let (Some(metrics), Some(start)) = (self.debug.as_mut(), query_start) {
+            metrics.last_query = Some(start.elapsed());
+        }
+
+        self.posts_loaded = posts.len();
+        self.more_posts = page_size.is_some_and(|size| posts.len() == size);
+        self.filter_loaded_posts(&mut posts);
+        self.posts = posts;
+        self.sort_posts();
+        if self.selected_index >= self.posts.len() && !self.posts.is_empty() {
+            self.selected_index = self.posts.len() - 1;
+        }
+    }
+
+    /// Append the next page of the active view, keeping the selection where it is
+    pub fn load_more_posts(&mut self) {
+        // Pages would bypass the filter; clearing it lets paging continue
+        if !self.more_posts || self.filter_base.is_some() {
+            return;
+        }
+        let db = lock_db(&self.db);
+        let (mut page, page_size) = self.query_posts(&db, self.posts_loaded);
+        drop(db);
+
+        self.posts_loaded += page.len();
+        self.more_posts = page_size.is_some_and(|size| page.len() == size);
+        self.filter_loaded_posts(&mut page);
+        // Posts inserted by a refresh since the last page shift the offsets
+        let loaded: HashSet<i64> = self.posts.iter().map(|p| p.id).collect();
+        page.retain(|p| !loaded.contains(&p.id));
+        self.posts.extend(page);
+        self.sort_posts();
+    }
+
+    /// One page of the active view starting at `offset`, with the page size.
+    /// Views ordered in memory (Fresh by category, Read Later) aren't paged and
+    /// return `None`, as do views whose limit is 0.
+    fn query_posts(&self, db: &Database, offset: usize) -> (Vec<Post>, Option<usize>) {
+        let limits = &self.config.limits;
+        let filter = |limit: usize| PostFilter {
+            only_unread: false,
+            only_bookmarked: false,
+            only_archived: false,
+            only_read_later: false,
+            category: None,
+            limit: LimitsConfig::cap(limit),
+            offset,
+        };
+
+        if let Some(term) = &self.search_term {
+            let limit = LimitsConfig::cap(limits.search);
+            return (db.search_posts(term, limit, offset).unwrap_or_default(), limit);
+        }
+
+        match &self.active_node {
             NavNode::SmartView(sv) => match sv {
                 SmartView::Fresh => {
                     if self.show_read {
-                        db.get_posts(PostFilter {
-                            only_unread: false,
-                            only_bookmarked: false,
-                            only_archived: false,
-                            only_read_later: false,
-                        })
-                        .unwrap_or_default()
+                        let limit = LimitsConfig::cap(limits.fresh_all);
+                        (db.get_posts(filter(limits.fresh_all)).unwrap_or_default(), limit)
                     } else {
-                        db.get_fresh_feed(15).unwrap_or_default()
+                        (db.get_fresh_feed(limits.fresh_per_category).unwrap_or_default(), None)
                     }
                 }
-                SmartView::Starred => db
-                    .get_posts(PostFilter {
-                        only_unread: false,
-                        only_bookmarked: true,
-                        only_archived: false,
-                        only_read_later: false,
-                    })
-                    .unwrap_or_default(),
-                SmartView::ReadLater => db
-                    .get_posts(PostFilter {
-                        only_unread: false,
-                        only_bookmarked: false,
-                        only_archived: false,
-                        only_read_later: true,
-                    })
-                    .unwrap_or_default(),
-                SmartView::Archived => db
-                    .get_posts(PostFilter {
-                        only_unread: false,
-                        only_bookmarked: false,
-                        only_archived: true,
-                        only_read_later: false,
-                    })
-                    .unwrap_or_default(),
+                SmartView::AllFeeds => {
+                    let limit = LimitsConfig::cap(limits.all_feeds);
+                    let posts = db
+                        .get_all_feeds_posts(limits.all_feeds_per_feed, limit, offset)
+                        .unwrap_or_default();
+                    (posts, limit)
+                }
+                SmartView::Starred => {
+                    let posts = db
+                        .get_posts(PostFilter { only_bookmarked: true, ..filter(limits.starred) })
+                        .unwrap_or_default();
+                    (posts, LimitsConfig::cap(limits.starred))
+                }
+                SmartView::ReadLater => {
+                    // Cut after ordering, so the limit shows the front of the queue
+                    let mut posts = read_later_queue(db);
+                    if !self.config.ui.read_later_oldest_first {
+                        posts.reverse();
+                    }
+                    if let Some(limit) = LimitsConfig::cap(limits.read_later) {
+                        posts.truncate(limit);
+                    }
+                    (posts, None)
+                }
+                SmartView::Archived => {
+                    let posts = db
+                        .get_posts(PostFilter { only_archived: true, ..filter(limits.archived) })
+                        .unwrap_or_default();
+                    (posts, LimitsConfig::cap(limits.archived))
+                }
             },
-            NavNode::Category(cat) => db.get_posts_by_category(cat).unwrap_or_default(),
-        };
+            NavNode::Category(cat) => {
+                let limit = LimitsConfig::cap(limits.category);
+                (db.get_posts_by_category(cat, limit, offset).unwrap_or_default(), limit)
+            }
+        }
+    }
 
-        self.posts = posts;
-        if self.selected_index >= self.posts.len() && !self.posts.is_empty() {
-            self.selected_index = self.posts.len() - 1;
+    fn filter_loaded_posts(&self, posts: &mut Vec<Post>) {
+        posts.retain(|p| !self.config.filters.is_blocked(&p.title));
+        if self.search_term.is_none() && matches!(self.active_node, NavNode::SmartView(_)) {
+            posts.retain(|p| self.sub_filter.matches(p));
         }
     }
 
     pub fn refresh_sidebar(&mut self) {
-        let db = self.db.lock().unwrap();
-        self.sidebar.load_categories(&db);
-        self.sidebar.update_counts(&db);
+        {
+            let db = lock_db(&self.db);
+            self.sidebar.load_categories(&db);
+            self.sidebar.update_counts(&db);
+            self.unhealthy_feeds = db.get_unhealthy_feeds_count().unwrap_or(0);
+        }
+        self.refresh_stats();
+    }
+
+    pub fn refresh_feed_health(&mut self) {
+        self.unhealthy_feeds = lock_db(&self.db).get_unhealthy_feeds_count().unwrap_or(0);
     }
 
     pub fn next_post(&mut self) {
         if !self.posts.is_empty() {
             if self.selected_index < self.posts.len() - 1 {
                 self.selected_index += 1;
+                self.mark_seen_before_selection();
+            }
+            if self.selected_index + 1 == self.posts.len() {
+                self.load_more_posts();
             }
         }
     }
 
+    /// Move the post selection by half the visible list (Ctrl+D / Ctrl+U)
+    pub fn half_page_posts(&mut self, down: bool) {
+        let half = (self.posts_area.height.saturating_sub(2) / 2).max(1) as usize;
+        if self.posts.is_empty() {
+            return;
+        }
+        if down {
+            self.selected_index = (self.selected_index + half).min(self.posts.len() - 1);
+            self.mark_seen_before_selection();
+            if self.selected_index + 1 == self.posts.len() {
+                self.load_more_posts();
+            }
+        } else {
+            self.selected_index = self.selected_index.saturating_sub(half);
+        }
+    }
+
+    pub fn select_first_post(&mut self) {
+        self.selected_index = 0;
+    }
+
+    pub fn select_last_post(&mut self) {
+        if !self.posts.is_empty() {
+            self.selected_index = self.posts.len() - 1;
+            self.mark_seen_before_selection();
+        }
+    }
+
+    /// Scroll the article by half its visible height (Ctrl+D / Ctrl+U)
+    pub fn half_page_article(&mut self, down: bool) {
+        let half = (self.article_view_height / 2).max(1);
+        if down {
+            self.scroll_offset = self.scroll_offset.saturating_add(half);
+            self.mark_read_if_scrolled_to_end();
+        } else {
+            self.scroll_offset = self.scroll_offset.saturating_sub(half);
+        }
+    }
+
+    /// Scroll so the last line of the article sits at the bottom of the view
+    pub fn scroll_article_to_end(&mut self) {
+        self.scroll_offset = self.article_lines.saturating_sub(self.article_view_height);
+        self.mark_read_if_scrolled_to_end();
+    }
+
+    /// Record everything above the selection as scrolled past
+    fn mark_seen_before_selection(&mut self) {
+        let end = self.selected_index.min(self.posts.len());
+        self.seen_posts.extend(self.posts[..end].iter().map(|p| p.id));
+    }
+
+    pub fn is_seen(&self, post: &Post) -> bool {
+        self.seen_posts.contains(&post.id)
+    }
+
     pub fn previous_post(&mut self) {
         if self.selected_index > 0 {
             self.selected_index -= 1;
@@ -238,28 +1118,271 @@ impl App {
     }
 
     pub fn open_article(&mut self) {
-        if let Some(post) = self.posts.get(self.selected_index) {
-            let _ = self.db.lock().unwrap().mark_as_read(post.id);
-            self.posts[self.selected_index].is_read = true;
+        if self.posts.get(self.selected_index).is_some() {
             self.focus = FocusPane::Article;
             self.scroll_offset = 0;
+            self.h_scroll_offset = 0;
+            self.auto_scroll = None;
+            self.article_lines = 0;
+            self.article_view_height = 0;
 
-            if !self.show_read {
-                if let NavNode::SmartView(SmartView::Fresh) = &self.active_node {
-                    self.refresh_sidebar();
+            if self.config.app.mark_read_on == MarkReadMode::Open {
+                self.mark_open_article_read();
+            }
+        }
+    }
+
+    fn mark_open_article_read(&mut self) {
+        let Some(post) = self.posts.get(self.selected_index) else {
+            return;
+        };
+        if post.is_read {
+            return;
+        }
+        let _ = lock_db(&self.db).mark_as_read(post.id);
+        self.posts[self.selected_index].is_read = true;
+
+        if !self.show_read
+            && let NavNode::SmartView(SmartView::Fresh) = &self.active_node
+        {
+            self.refresh_sidebar();
+        }
+    }
+
+    /// Re-read the config file and apply it without restarting. The theme and
+    /// key bindings are picked up on the next draw; the refresh interval keeps its startup value.
+    pub fn reload_config(&mut self) {
+        match config::load_config_from_path(&self.config_path) {
+            Ok((config, unknown_keys)) => {
+                self.show_summary = config.ui.prefer_summary;
+                self.show_banner = config.ui.show_ascii_banner;
+                if config.feeds.max_fulltext_fetches != self.config.feeds.max_fulltext_fetches {
+                    self.fulltext_permits = Arc::new(Semaphore::new(config.feeds.max_fulltext_fetches.max(1)));
                 }
+                self.sidebar.all_feeds_per_feed = config.limits.all_feeds_per_feed;
+                self.theme = ThemeVariant::from_str(&config.app.theme);
+                self.keymap = KeyMap::new(&config.keybindings.actions);
+                self.config = config;
+                self.config_recovered = false;
+                self.refresh_sidebar();
+                self.reload_posts_for_active_node();
+                self.message = Some(unknown_keys.map_or("Config reloaded".to_string(), |note| format!("Config reloaded. {}", note)));
+            }
+            Err(e) => {
+                let error = e.to_string();
+                self.message = Some(format!("Config not reloaded: {}", error.lines().next().unwrap_or_default()));
+            }
+        }
+    }
+
+    /// Switch to the next theme and save it as the configured theme. A config
+    /// salvaged by `recover_config` is never written back, as that would
+    /// replace the user's broken file with defaults.
+    pub fn cycle_theme(&mut self) {
+        self.theme = self.theme.next(&self.config.theme);
+        self.config.app.theme = self.theme.as_str().to_string();
+        let saved = if self.config_recovered {
+            Err("the config file has errors".into())
+        } else {
+            config::save_theme_to_path(self.theme.as_str(), &self.config_path)
+        };
+        self.message = Some(match saved {
+            Ok(()) => format!("Theme: {}", self.theme.as_str()),
+            Err(e) => format!("Theme: {} (not saved: {})", self.theme.as_str(), e),
+        });
+    }
+
+    /// Store text extracted from a post's web page, unless it's no longer than
+    /// what the feed already gave us (a paywall or teaser page). A `live`
+    /// fetch is only shown, next to the saved copy, until saved with `save_live_page`.
+    pub fn apply_full_text(&mut self, post_id: i64, result: Result<String, String>, live: bool) {
+        self.fulltext_loading.remove(&post_id);
+        let Some(post) = self.posts.iter_mut().find(|p| p.id == post_id) else {
+            return;
+        };
+
+        let html = match result {
+            Ok(html) => html,
+            Err(e) => {
+                self.message = Some(format!("Couldn't load the full article: {}", e));
+                return;
             }
+        };
+        if live {
+            self.live_pages.insert(post_id, html);
+            self.show_live_page = true;
+            self.scroll_offset = 0;
+            self.message = Some("Showing the live page │ L:Saved copy │ ctrl+s:Save it".to_string());
+            return;
+        }
+        let existing = post.content.as_deref().or(post.summary.as_deref()).unwrap_or_default();
+        if reading::word_count(&html) <= reading::word_count(existing) {
+            self.message = Some("The web page has no more text than the feed; keeping it".to_string());
+            return;
+        }
+
+        if lock_db(&self.db).update_post_content(post_id, &html).is_ok() {
+            post.reading_minutes = reading::reading_minutes(&html);
+            post.content = Some(html);
+            self.fulltext_loaded.insert(post_id);
+            self.show_summary = false;
+            self.scroll_offset = 0;
+            self.message = Some("Loaded the full article".to_string());
+        }
+    }
+
+    pub fn toggle_article_wrap(&mut self) {
+        self.article_wrap = !self.article_wrap;
+        self.h_scroll_offset = 0;
+        self.message = Some(if self.article_wrap {
+            "Line wrap on".to_string()
+        } else {
+            "Line wrap off (←/→ to scroll)".to_string()
+        });
+    }
+
+    /// Switch the article view between the full content and the feed summary
+    pub fn toggle_summary(&mut self) {
+        self.show_summary = !self.show_summary;
+        self.scroll_offset = 0;
+        self.message = Some(if self.show_summary {
+            "Showing summary".to_string()
+        } else {
+            "Showing full content".to_string()
+        });
+    }
+
+    /// In scroll-end mode, mark the open article read once its last lines are on screen
+    pub fn mark_read_if_scrolled_to_end(&mut self) {
+        if self.config.app.mark_read_on != MarkReadMode::ScrollEnd || self.article_view_height == 0 {
+            return;
+        }
+        // A couple of lines of slack so trailing footers don't have to be scrolled past
+        let visible_end = self.scroll_offset.saturating_add(self.article_view_height).saturating_add(2);
+        if visible_end >= self.article_lines {
+            self.mark_open_article_read();
+        }
+    }
+
+    pub fn toggle_auto_scroll(&mut self) {
+        if self.auto_scroll.take().is_some() {
+            self.message = Some("Auto-scroll off".to_string());
+        } else {
+            self.auto_scroll = Some(AutoScroll::default());
+            self.message = Some(format!(
+                "Auto-scroll at {} lines/s (Space to pause)",
+                self.config.ui.auto_scroll_lines_per_sec
+            ));
+        }
+    }
+
+    pub fn set_auto_scroll_paused(&mut self, paused: bool) {
+        if let Some(auto_scroll) = self.auto_scroll.as_mut() {
+            auto_scroll.paused = paused;
+        }
+    }
+
+    pub fn is_auto_scrolling(&self) -> bool {
+        self.auto_scroll.as_ref().is_some_and(|a| !a.paused)
+    }
+
+    /// Advance the auto-scroll by `elapsed`, stopping once the article end is on screen
+    pub fn advance_auto_scroll(&mut self, elapsed: Duration) {
+        // Not drawn yet, so the article length is unknown
+        if self.article_view_height == 0 {
+            return;
+        }
+        let rate = self.config.ui.auto_scroll_lines_per_sec.max(0.0);
+        let Some(auto_scroll) = self.auto_scroll.as_mut().filter(|a| !a.paused) else {
+            return;
+        };
+
+        auto_scroll.carry += rate * elapsed.as_secs_f64();
+        let lines = auto_scroll.carry.floor();
+        auto_scroll.carry -= lines;
+
+        let max_offset = self.article_lines.saturating_sub(self.article_view_height);
+        self.scroll_offset = self.scroll_offset.saturating_add(lines as u16).min(max_offset);
+        if self.scroll_offset >= max_offset {
+            self.auto_scroll = None;
+            self.message = Some("Auto-scroll reached the end".to_string());
         }
+        self.mark_read_if_scrolled_to_end();
+    }
+
+    /// Move to the next post while staying in the article view
+    pub fn open_next_article(&mut self) {
+        if self.selected_index + 1 >= self.posts.len() {
+            self.load_more_posts();
+        }
+        if self.selected_index + 1 >= self.posts.len() {
+            self.message = Some("Last post in this view".to_string());
+            return;
+        }
+        self.selected_index += 1;
+        self.mark_seen_before_selection();
+        self.open_article();
+    }
+
+    pub fn open_previous_article(&mut self) {
+        if self.selected_index == 0 {
+            self.message = Some("First post in this view".to_string());
+            return;
+        }
+        self.selected_index -= 1;
+        self.open_article();
+    }
+
+    /// Switch the open article between its saved text and the live page
+    /// fetched this session. Returns false when there's no live page yet, so
+    /// the caller can fetch one.
+    pub fn toggle_live_page(&mut self) -> bool {
+        let Some(post) = self.posts.get(self.selected_index) else {
+            return true;
+        };
+        if !self.live_pages.contains_key(&post.id) {
+            return false;
+        }
+        self.show_live_page = !self.show_live_page;
+        self.scroll_offset = 0;
+        self.message = Some(if self.show_live_page {
+            "Showing the live page".to_string()
+        } else {
+            "Showing the saved copy".to_string()
+        });
+        true
+    }
+
+    /// Replace the saved text of the open article with the live page being shown
+    pub fn save_live_page(&mut self) {
+        let Some(post) = self.posts.get_mut(self.selected_index) else {
+            return;
+        };
+        if !self.show_live_page || !self.live_pages.contains_key(&post.id) {
+            self.message = Some("No live page shown; L fetches one".to_string());
+            return;
+        }
+        let html = self.live_pages.remove(&post.id).unwrap_or_default();
+        self.message = Some(match lock_db(&self.db).update_post_content(post.id, &html) {
+            Ok(()) => {
+                post.reading_minutes = reading::reading_minutes(&html);
+                post.content = Some(html);
+                self.show_summary = false;
+                "Saved the live page in place of the old copy".to_string()
+            }
+            Err(e) => format!("Couldn't save the live page: {}", e),
+        });
     }
 
     pub fn close_article(&mut self) {
         self.focus = FocusPane::Posts;
         self.scroll_offset = 0;
+        self.auto_scroll = None;
 
-        if !self.show_read {
-            if let NavNode::SmartView(SmartView::Fresh) = &self.active_node {
-                self.remove_read_posts();
-            }
+        if !self.show_read
+            && let NavNode::SmartView(SmartView::Fresh) = &self.active_node
+        {
+            self.remove_read_posts();
         }
     }
 
@@ -281,7 +1404,7 @@ impl App {
 
     pub fn toggle_bookmark(&mut self) {
         if let Some(post) = self.posts.get_mut(self.selected_index) {
-            let _ = self.db.lock().unwrap().toggle_bookmark(post.id);
+            let _ = lock_db(&self.db).toggle_bookmark(post.id);
             post.is_bookmarked = !post.is_bookmarked;
 
             self.message = Some(if post.is_bookmarked {
@@ -290,12 +1413,12 @@ impl App {
                 "Removed from Starred".to_string()
             });
 
-            if !post.is_bookmarked {
-                if let NavNode::SmartView(SmartView::Starred) = &self.active_node {
-                    self.posts.remove(self.selected_index);
-                    if self.selected_index >= self.posts.len() && !self.posts.is_empty() {
-                        self.selected_index = self.posts.len() - 1;
-                    }
+            if !post.is_bookmarked
+                && let NavNode::SmartView(SmartView::Starred) = &self.active_node
+            {
+                self.posts.remove(self.selected_index);
+                if self.selected_index >= self.posts.len() && !self.posts.is_empty() {
+                    self.selected_index = self.posts.len() - 1;
                 }
             }
             self.refresh_sidebar();
@@ -304,7 +1427,7 @@ impl App {
 
     pub fn toggle_archived(&mut self) {
         if let Some(post) = self.posts.get_mut(self.selected_index) {
-            let _ = self.db.lock().unwrap().mark_as_archived(post.id);
+            let _ = lock_db(&self.db).mark_as_archived(post.id);
             post.is_archived = !post.is_archived;
 
             self.message = Some(if post.is_archived {
@@ -313,12 +1436,12 @@ impl App {
                 "Unarchived".to_string()
             });
 
-            if !post.is_archived {
-                if let NavNode::SmartView(SmartView::Archived) = &self.active_node {
-                    self.posts.remove(self.selected_index);
-                    if self.selected_index >= self.posts.len() && !self.posts.is_empty() {
-                        self.selected_index = self.posts.len() - 1;
-                    }
+            if !post.is_archived
+                && let NavNode::SmartView(SmartView::Archived) = &self.active_node
+            {
+                self.posts.remove(self.selected_index);
+                if self.selected_index >= self.posts.len() && !self.posts.is_empty() {
+                    self.selected_index = self.posts.len() - 1;
                 }
             }
             self.refresh_sidebar();
@@ -327,8 +1450,9 @@ impl App {
 
     pub fn toggle_read_later(&mut self) {
         if let Some(post) = self.posts.get_mut(self.selected_index) {
-            let _ = self.db.lock().unwrap().mark_as_read_later(post.id);
+            let _ = lock_db(&self.db).mark_as_read_later(post.id);
             post.is_read_later = !post.is_read_later;
+            post.read_later_at = if post.is_read_later { Some(chrono::Utc::now()) } else { None };
 
             self.message = Some(if post.is_read_later {
                 "󰃰 Added to Read Later".to_string()
@@ -336,12 +1460,12 @@ impl App {
                 "Removed from Read Later".to_string()
             });
 
-            if !post.is_read_later {
-                if let NavNode::SmartView(SmartView::ReadLater) = &self.active_node {
-                    self.posts.remove(self.selected_index);
-                    if self.selected_index >= self.posts.len() && !self.posts.is_empty() {
-                        self.selected_index = self.posts.len() - 1;
-                    }
+            if !post.is_read_later
+                && let NavNode::SmartView(SmartView::ReadLater) = &self.active_node
+            {
+                self.posts.remove(self.selected_index);
+                if self.selected_index >= self.posts.len() && !self.posts.is_empty() {
+                    self.selected_index = self.posts.len() - 1;
                 }
             }
             self.refresh_sidebar();
@@ -352,9 +1476,9 @@ impl App {
         if let Some(post) = self.posts.get_mut(self.selected_index) {
             let new_state = !post.is_read;
             if new_state {
-                let _ = self.db.lock().unwrap().mark_as_read(post.id);
+                let _ = lock_db(&self.db).mark_as_read(post.id);
             } else {
-                let _ = self.db.lock().unwrap().mark_as_unread(post.id);
+                let _ = lock_db(&self.db).mark_as_unread(post.id);
             }
             post.is_read = new_state;
 
@@ -364,12 +1488,13 @@ impl App {
                 "Marked as unread".to_string()
             });
 
-            if !self.show_read && new_state {
-                if let NavNode::SmartView(SmartView::Fresh) = &self.active_node {
-                    self.posts.remove(self.selected_index);
-                    if self.selected_index >= self.posts.len() && !self.posts.is_empty() {
-                        self.selected_index = self.posts.len() - 1;
-                    }
+            if !self.show_read
+                && new_state
+                && let NavNode::SmartView(SmartView::Fresh) = &self.active_node
+            {
+                self.posts.remove(self.selected_index);
+                if self.selected_index >= self.posts.len() && !self.posts.is_empty() {
+                    self.selected_index = self.posts.len() - 1;
                 }
             }
             self.refresh_sidebar();
@@ -381,7 +1506,7 @@ impl App {
         if let Some(post) = self.posts.get(self.selected_index) {
             let post_title = post.title.clone();
             let post_id = post.id;
-            if self.db.lock().unwrap().delete_post(post_id).is_ok() {
+            if lock_db(&self.db).delete_post(post_id).is_ok() {
                 self.posts.remove(self.selected_index);
                 if self.selected_index >= self.posts.len() && !self.posts.is_empty() {
                     self.selected_index = self.posts.len() - 1;
@@ -397,7 +1522,7 @@ impl App {
         if let Some(feed) = self.feeds.get(self.selected_feed_index) {
             let feed_url = feed.url.clone();
             let feed_id = feed.id;
-            if self.db.lock().unwrap().delete_feed(feed_id).is_ok() {
+            if lock_db(&self.db).delete_feed(feed_id).is_ok() {
                 self.reload_feeds();
                 self.refresh_sidebar();
                 self.reload_posts_for_active_node();
@@ -407,15 +1532,55 @@ impl App {
     }
 
     pub fn reload_feeds(&mut self) {
-        self.feeds = self.db.lock().unwrap().get_feeds().unwrap_or_default();
+        self.feeds = lock_db(&self.db).get_feeds().unwrap_or_default();
         if self.selected_feed_index >= self.feeds.len() && !self.feeds.is_empty() {
             self.selected_feed_index = self.feeds.len() - 1;
         }
     }
 
-    pub fn add_feed(&mut self, url: &str, category: &str) {
+    /// Turn pasted input into the URL to validate, mapping YouTube channel and
+    /// playlist pages to their feeds. `None` (with a message) if unusable.
+    pub fn resolve_feed_url(&mut self, input: &str) -> Option<String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+        if !rss::is_youtube_url(input) {
+            return Some(input.to_string());
+        }
+        let resolved = rss::youtube_feed_url(input);
+        if resolved.is_none() {
+            self.message = Some("Use a YouTube channel (/channel/UC…) or playlist URL".to_string());
+        }
+        resolved
+    }
+
+    /// Subscribe to an already validated `url` in `category`, storing its
+    /// title (or the hostname when the feed has none)
+    pub fn add_feed(&mut self, url: &str, category: &str, title: Option<&str>) {
         if !url.trim().is_empty() {
-            if self.db.lock().unwrap().add_feed_with_category(url, category).is_ok() {
+            let existing = lock_db(&self.db).find_feed_by_url(url).ok().flatten();
+            if let Some(feed) = existing {
+                if feed.category == category {
+                    self.message = Some(format!("Already subscribed in '{}'", feed.category));
+                } else {
+                    self.input_mode = InputMode::Confirming(ConfirmAction::MoveFeed {
+                        feed_id: feed.id,
+                        from: feed.category,
+                        to: category.to_string(),
+                    });
+                }
+                return;
+            }
+
+            let added = {
+                let db = lock_db(&self.db);
+                db.add_feed_with_category(url, category).inspect(|&feed_id| {
+                    let fallback = rss::feed_host(url).unwrap_or_else(|| url.to_string());
+                    let _ = db.update_feed_title(feed_id, title.unwrap_or(&fallback));
+                })
+            };
+            if added.is_ok() {
                 self.reload_feeds();
                 self.refresh_sidebar();
                 self.message = Some(format!("Added feed: {}", truncate_str(url, 40)));
@@ -423,12 +1588,49 @@ impl App {
         }
     }
 
-    pub fn add_category(&mut self, name: &str) {
-        if !name.trim().is_empty() {
-            if self.db.lock().unwrap().add_category(name).is_ok() {
-                self.refresh_sidebar();
-                self.message = Some(format!("Added category: {}", name));
+    /// Apply the outcome of validating a pasted feed URL
+    pub fn handle_feed_check(&mut self, check: FeedCheck) {
+        match check {
+            FeedCheck::Valid { url, title } => {
+                self.pending_feed_url = Some(url);
+                self.pending_feed_title = title;
+                self.input_mode = InputMode::SelectingCategory;
+                self.message = None;
             }
+            FeedCheck::Discovered(mut feeds) => {
+                if feeds.len() == 1 {
+                    let DiscoveredFeed { url, title } = feeds.remove(0);
+                    self.input_mode = InputMode::Confirming(ConfirmAction::UseDiscoveredFeed { url, title });
+                } else {
+                    self.discovered_feeds = feeds;
+                    self.discovered_feed_index = 0;
+                    self.input_mode = InputMode::ChoosingDiscoveredFeed;
+                }
+                self.message = None;
+            }
+            FeedCheck::Invalid { url, error } => {
+                self.input_mode = InputMode::Normal;
+                self.message = Some(format!("Can't add {}: {}", truncate_str(&url, 40), error));
+            }
+        }
+    }
+
+    /// Take the highlighted discovered feed on to the category step
+    pub fn choose_discovered_feed(&mut self) {
+        let feeds = std::mem::take(&mut self.discovered_feeds);
+        if let Some(feed) = feeds.into_iter().nth(self.discovered_feed_index) {
+            self.pending_feed_url = Some(feed.url);
+            self.pending_feed_title = feed.title;
+            self.input_mode = InputMode::SelectingCategory;
+        } else {
+            self.input_mode = InputMode::Normal;
+        }
+    }
+
+    pub fn add_category(&mut self, name: &str) {
+        if !name.trim().is_empty() && lock_db(&self.db).add_category(name).is_ok() {
+            self.refresh_sidebar();
+            self.message = Some(format!("Added category: {}", name));
         }
     }
 
@@ -436,7 +1638,7 @@ impl App {
     pub fn delete_selected_category(&mut self) {
         if let Some(cat) = self.sidebar.categories.get(self.sidebar.category_index).cloned() {
             if cat != "General" {
-                if self.db.lock().unwrap().delete_category(&cat).is_ok() {
+                if lock_db(&self.db).delete_category(&cat).is_ok() {
                     self.refresh_sidebar();
                     self.reload_posts_for_active_node();
                     self.message = Some(format!("Deleted category: {}", cat));
@@ -447,8 +1649,23 @@ impl App {
         }
     }
 
+    pub fn move_category_feeds(&mut self, from: &str, to: &str) {
+        if from == to {
+            self.message = Some("Pick a different category".to_string());
+            return;
+        }
+        let result = lock_db(&self.db).move_category_feeds(from, to);
+        if let Ok(moved) = result {
+            self.reload_feeds();
+            self.refresh_sidebar();
+            self.reload_posts_for_active_node();
+            self.message = Some(format!("Moved {} feeds from '{}' to '{}'", moved, from, to));
+        }
+    }
+
     pub fn toggle_show_read(&mut self) {
         self.show_read = !self.show_read;
+        let _ = lock_db(&self.db).set_preference(SHOW_READ_PREFERENCE, &self.show_read.to_string());
         self.reload_posts_for_active_node();
         self.message = Some(if self.show_read {
             "Showing all posts".to_string()
@@ -457,14 +1674,134 @@ impl App {
         });
     }
 
+    /// The selected post's link, or `None` when it's missing or not a valid URL
+    fn selected_post_link(&self) -> Option<&str> {
+        let post = self.posts.get(self.selected_index)?;
+        let url = post.url.trim();
+        if url.is_empty() || reqwest::Url::parse(url).is_err() {
+            None
+        } else {
+            Some(url)
+        }
+    }
+
+    pub fn open_in_browser(&mut self) {
+        if self.posts.get(self.selected_index).is_none() {
+            return;
+        }
+        self.message = Some(match self.selected_post_link() {
+            Some(url) => match open_url(url, self.config.app.open_in_background) {
+                Ok(_) => "Opened in browser".to_string(),
+                Err(e) => format!("Failed to open browser: {}", e),
+            },
+            None => "No link for this post".to_string(),
+        });
+    }
+
+    /// Hand the post's podcast episode or video to the system's default player
+    pub fn play_enclosure(&mut self) {
+        let Some(post) = self.posts.get(self.selected_index) else {
+            return;
+        };
+        self.message = Some(match &post.enclosure_url {
+            Some(url) => match open::that(url) {
+                Ok(_) => "Opened the episode in the default player".to_string(),
+                Err(e) => format!("Failed to open the episode: {}", e),
+            },
+            None => "This post has no audio or video attached".to_string(),
+        });
+    }
+
+    /// Open the data directory (and the config directory, when separate) in the file manager
+    pub fn open_data_dirs(&mut self) {
+        let mut dirs = vec![self.data_dir.clone()];
+        if let Some(config_dir) = self.config_path.parent()
+            && config_dir != self.data_dir
+            && !config_dir.as_os_str().is_empty()
+        {
+            dirs.push(config_dir.to_path_buf());
+        }
+
+        let mut opened = Vec::new();
+        for dir in &dirs {
+            if let Err(e) = open::that(dir) {
+                self.message = Some(format!("Failed to open {}: {}", dir.display(), e));
+                return;
+            }
+            opened.push(dir.display().to_string());
+        }
+        self.message = Some(format!("Opened {}", opened.join(" and ")));
+    }
+
+    /// Put `text` on the system clipboard, falling back to OSC 52 when no
+    /// clipboard is reachable. Returns the status message to show for `what`.
+    fn copy_to_clipboard(&mut self, text: &str, what: &str) -> String {
+        if self.clipboard.is_none() {
+            self.clipboard = arboard::Clipboard::new().ok();
+        }
+        // The clipboard is kept alive: on X11 the contents go away with it
+        if let Some(clipboard) = self.clipboard.as_mut()
+            && clipboard.set_text(text).is_ok()
+        {
+            return format!("{} copied to clipboard", what);
+        }
+
+        match copy_via_osc52(text) {
+            Ok(()) => format!("{} sent to the terminal clipboard (OSC 52)", what),
+            Err(e) => format!("Couldn't copy {}: {}", what.to_lowercase(), e),
+        }
+    }
+
     pub fn copy_url_to_clipboard(&mut self) {
-        if let Some(post) = self.posts.get(self.selected_index) {
-            let url = &post.url;
-            print!("\x1b]52;c;{}\x07", base64_encode(url));
-            self.message = Some("URL copied to clipboard".to_string());
+        if self.posts.get(self.selected_index).is_none() {
+            return;
+        }
+        let message = match self.selected_post_link().map(str::to_string) {
+            Some(url) => self.copy_to_clipboard(&url, "URL"),
+            None => "No link for this post".to_string(),
+        };
+        self.message = Some(message);
+    }
+
+    pub fn export_current_view(&mut self, format: ExportFormat) {
+        if self.posts.is_empty() {
+            self.message = Some("Nothing to export".to_string());
+            return;
+        }
+
+        let title = self.active_node.title();
+        self.message = Some(match export::export_posts(&self.data_dir, &title, &self.posts, format) {
+            Ok(path) => format!("Exported {} posts to {}", self.posts.len(), path.display()),
+            Err(e) => format!("Export failed: {}", e),
+        });
+    }
+
+    /// Share the current view as a digest, on the clipboard or in a file per config
+    pub fn share_digest(&mut self) {
+        if self.posts.is_empty() {
+            self.message = Some("Nothing to share".to_string());
+            return;
+        }
+
+        let title = self.active_node.title();
+        let format = self.config.app.digest_format;
+        if self.config.app.digest_to_file {
+            self.export_current_view(ExportFormat::Digest(format));
+        } else {
+            let digest = export::posts_to_digest(&title, &self.posts, format);
+            let what = format!("Digest of {} posts", self.posts.len());
+            self.message = Some(self.copy_to_clipboard(&digest, &what));
         }
     }
 
+    pub fn copy_diagnostics_to_clipboard(&mut self) {
+        let report = {
+            let db = lock_db(&self.db);
+            diagnostics::collect(&self.config_path, &self.db_path, Some(&db))
+        };
+        self.message = Some(self.copy_to_clipboard(&report, "Diagnostics"));
+    }
+
     pub fn get_selected_category(&self) -> String {
         self.sidebar
             .categories
@@ -474,27 +1811,148 @@ impl App {
     }
 }
 
-fn base64_encode(input: &str) -> String {
-    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-    let bytes = input.as_bytes();
-    let mut result = String::new();
+/// Open a URL in the default browser. With `background` set, ask the
+/// platform not to raise the browser where that's supported (macOS `open -g`);
+/// elsewhere this is a normal open.
+fn open_url(url: &str, background: bool) -> std::io::Result<()> {
+    if background && cfg!(target_os = "macos") {
+        let status = std::process::Command::new("open").arg("-g").arg(url).status()?;
+        if status.success() {
+            return Ok(());
+        }
+    }
+    open::that(url)
+}
+
+/// Copy text via the OSC 52 escape sequence, for terminals reached over SSH
+/// or systems without a clipboard service. Whether the terminal honours it
+/// can't be detected.
+fn copy_via_osc52(text: &str) -> std::io::Result<()> {
+    use base64::Engine;
+    use std::io::Write;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = std::io::stdout().lock();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()
+}
+
+/// Every Read Later post in queue order, oldest saved first
+fn read_later_queue(db: &Database) -> Vec<Post> {
+    let mut posts = db
+        .get_posts(PostFilter {
+            only_unread: false,
+            only_bookmarked: false,
+            only_archived: false,
+            only_read_later: true,
+            category: None,
+            limit: None,
+            offset: 0,
+        })
+        .unwrap_or_default();
+    posts.sort_by_key(|p| (p.read_later_order, p.read_later_at));
+    posts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_str_leaves_short_text_alone() {
+        assert_eq!(truncate_str("Rust 2024", 30), "Rust 2024");
+    }
+
+    #[test]
+    fn truncate_str_cuts_ascii_to_max_len() {
+        assert_eq!(truncate_str("abcdefghij", 8), "abcde...");
+    }
+
+    #[test]
+    fn truncate_str_cuts_multibyte_titles_on_char_boundaries() {
+        // Each of these is several bytes wide; a byte slice would split one
+        let title = "Привет, мир — ニュース 🎉🎉🎉";
+        let cut = truncate_str(title, 10);
+        assert_eq!(cut, "Привет,...");
+        assert_eq!(cut.chars().count(), 10);
+        assert_eq!(truncate_str("🎉🎉🎉🎉🎉", 4), "🎉...");
+    }
 
-    for chunk in bytes.chunks(3) {
-        let mut n: u32 = 0;
-        for (i, &byte) in chunk.iter().enumerate() {
-            n |= (byte as u32) << (16 - 8 * i);
+    /// An app on an in-memory database holding one feed with posts titled
+    /// `titles`, newest first by publish date
+    fn app_with_posts(config: &str, titles: &[&str]) -> App {
+        let db = Database::init_with_path(":memory:").unwrap();
+        let feed = db.add_feed("https://example.com/feed").unwrap();
+        let now = chrono::Utc::now();
+        for (i, title) in titles.iter().enumerate() {
+            let url = format!("https://example.com/{}", i);
+            let pub_date = now - chrono::Duration::minutes(i as i64);
+            db.insert_post(feed, title, &url, None, None, Some(pub_date)).unwrap();
         }
+        App::new(db, toml::from_str(config).unwrap())
+    }
 
-        let chars_to_output = chunk.len() + 1;
-        for i in 0..4 {
-            if i < chars_to_output {
-                let idx = ((n >> (18 - 6 * i)) & 0x3F) as usize;
-                result.push(ALPHABET[idx] as char);
-            } else {
-                result.push('=');
+    fn titles(app: &App) -> Vec<&str> {
+        app.posts.iter().map(|p| p.title.as_str()).collect()
+    }
+
+    #[test]
+    fn read_later_limit_keeps_the_front_of_the_queue() {
+        let mut app = app_with_posts("[feeds]\n[limits]\nread_later = 2\n", &["a", "b", "c", "d"]);
+        {
+            let db = lock_db(&app.db);
+            let mut posts = db.get_posts_by_feed(1).unwrap();
+            posts.sort_by_key(|p| p.title.clone());
+            // Saved newest-published first, so publish order and queue order disagree
+            for post in posts.iter().rev() {
+                db.mark_as_read_later(post.id).unwrap();
             }
         }
+        app.active_node = NavNode::SmartView(SmartView::ReadLater);
+        app.reload_posts_for_active_node();
+        assert_eq!(titles(&app), vec!["d", "c"]);
+
+        app.selected_index = 1;
+        app.move_read_later(-1);
+        assert_eq!(titles(&app), vec!["c", "d"]);
+
+        let queue: Vec<_> = read_later_queue(&lock_db(&app.db)).into_iter().map(|p| p.title).collect();
+        assert_eq!(queue, vec!["c", "d", "b", "a"]);
     }
 
-    result
+    #[test]
+    fn list_filter_keeps_changes_made_while_filtered() {
+        let mut app = app_with_posts("[feeds]\n", &["Rust news", "Go news", "Rust tips"]);
+        app.active_node = NavNode::SmartView(SmartView::AllFeeds);
+        app.reload_posts_for_active_node();
+        app.start_list_filter();
+        app.text_input.insert_str("rust");
+        app.apply_list_filter();
+        assert_eq!(titles(&app), vec!["Rust news", "Rust tips"]);
+
+        app.toggle_bookmark();
+        let tips = app.posts[1].id;
+        app.delete_post(tips);
+
+        // Widening the filter must not bring back the deleted post or drop the star
+        app.text_input.delete_char();
+        app.text_input.delete_char();
+        app.apply_list_filter();
+        assert_eq!(titles(&app), vec!["Rust news"]);
+        assert!(app.posts[0].is_bookmarked);
+
+        app.clear_list_filter();
+        assert_eq!(titles(&app), vec!["Rust news", "Go news"]);
+        assert!(app.posts[0].is_bookmarked);
+    }
+
+    #[test]
+    fn default_tab_overrides_the_last_view() {
+        let db = Database::init_with_path(":memory:").unwrap();
+        db.add_feed("https://example.com/feed").unwrap();
+        db.set_preference(LAST_NODE_PREFERENCE, "view:archived").unwrap();
+        let config = toml::from_str("[feeds]\n[ui]\ndefault_tab = \"read-later\"\n").unwrap();
+        let app = App::new(db, config);
+        assert_eq!(app.active_node, NavNode::SmartView(SmartView::ReadLater));
+    }
 }
@@ -1,4 +1,11 @@
-#[allow(dead_code)]
+pub const NEWS_BANNER: &str = r"
+ _   _ _______        _______
+| \ | | ____\ \      / / ____|
+|  \| |  _|  \ \ /\ / /\___ \
+| |\  | |___  \ V  V /  ___) |
+|_| \_|_____|  \_/\_/  |____/
+";
+
 pub const QUOTES: &[&str] = &[
     "\"Stay curious, keep reading.\"",
     "\"Knowledge is the new currency.\"",
@@ -14,7 +21,6 @@ pub const QUOTES: &[&str] = &[
     "\"Books are a uniquely portable magic.\"",
 ];
 
-#[allow(dead_code)]
 pub fn get_random_quote() -> &'static str {
     use std::time::{SystemTime, UNIX_EPOCH};
     let seed = SystemTime::now()
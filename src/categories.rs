@@ -16,10 +16,10 @@ impl CategorySelector {
     }
 
     pub fn load_from_db(&mut self, db: &Database) {
-        if let Ok(cats) = db.get_categories() {
-            if !cats.is_empty() {
-                self.categories = cats;
-            }
+        if let Ok(cats) = db.get_categories()
+            && !cats.is_empty()
+        {
+            self.categories = cats;
         }
         if !self.categories.contains(&"General".to_string()) {
             self.categories.insert(0, "General".to_string());
@@ -1,4 +1,6 @@
 use clap::{Parser, Subcommand};
+use crate::config::expand_path;
+use crate::theme::ColorMode;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -12,14 +14,31 @@ use std::path::PathBuf;
                                   Use a custom config file
     news --db-path ~/.local/share/news/feeds.db
                                   Use a custom database location
+    NEWS_DATA_DIR=~/Sync/news news
+                                  Keep the database in a synced folder
     news reset-db                 Reset the database (removes all feeds and posts)
     news export-feeds > feeds.opml
                                   Export feeds to OPML format
     news import-feeds feeds.opml  Import feeds from OPML file
+    news export-json -o backup.json
+                                  Back up feeds, posts and reading state
+    news import-json backup.json  Restore a JSON backup
+    news export-markdown --dir ./lists
+                                  Write a Markdown reading list per category
+    news import-feeds https://example.com/feeds.opml
+                                  Import feeds from an OPML URL
+    news refresh                  Fetch new posts headlessly (e.g. from cron)
+    news list-posts --unread -n 20
+                                  Print post ids, read state, feed and title
+    news open 42                  Open post 42 in the browser and mark it read
+    news mark-read Technology     Mark a category's posts read (or a feed URL, or --all)
+    news doctor                   Print diagnostics to paste into a bug report
+    news open-dirs                Open the config and data folders (for backups)
 
 KEYBINDINGS:
-    Tab/Shift+Tab    Navigate between tabs
-    1-6              Jump to specific tab
+    Tab/Shift+Tab    Move focus between the sidebar and the posts list
+    1-5              Jump to Fresh, All Feeds, Starred, Read Later or Archived
+    6                Open the feed manager
     j/Down           Move down in list
     k/Up             Move up in list
     Enter            Open article
@@ -45,14 +64,18 @@ pub struct Cli {
     #[arg(short, long)]
     pub verbose: bool,
 
-    /// Disable automatic feed updates on startup
+    /// Disable automatic feed updates (startup, periodic refresh and health checks)
     #[arg(long)]
     pub no_auto_update: bool,
 
-    /// Theme to use (overrides config file)
+    /// Theme to use: catppuccin-mocha, claude-code, gruvbox, nord or custom (overrides config file)
     #[arg(short, long, value_name = "THEME")]
     pub theme: Option<String>,
 
+    /// Color depth to render with (auto-detected from $COLORTERM/$TERM by default)
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub color: Option<ColorMode>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -71,15 +94,83 @@ pub enum Commands {
         /// Output file (defaults to stdout)
         #[arg(short, long, value_name = "FILE")]
         output: Option<PathBuf>,
+        /// Only export this category's feeds, nested under a folder outline
+        #[arg(long, value_name = "NAME")]
+        category: Option<String>,
     },
 
-    /// Import feeds from OPML file
+    /// Import feeds from an OPML file or URL, or a plain list of feed URLs
     ImportFeeds {
-        /// Input OPML file
+        /// OPML or one-URL-per-line file, or an http(s) URL to fetch it from
+        #[arg(value_name = "FILE_OR_URL")]
+        input: String,
+    },
+
+    /// Export all feeds and posts, with reading state, to JSON
+    ExportJson {
+        /// Output file (defaults to stdout)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Restore feeds and posts from an export-json backup (safe to repeat)
+    ImportJson {
+        /// Backup file written by export-json
         #[arg(value_name = "FILE")]
         input: PathBuf,
     },
 
+    /// Write one Markdown reading list per category
+    ExportMarkdown {
+        /// Directory to write the lists into
+        #[arg(short, long, value_name = "DIR", default_value = "lists")]
+        dir: PathBuf,
+
+        /// Only include unread posts
+        #[arg(short, long)]
+        unread_only: bool,
+    },
+
+    /// Fetch all feeds, or one category's, and store new posts without opening the TUI
+    Refresh {
+        /// Only fetch this category's feeds
+        #[arg(long, value_name = "NAME")]
+        category: Option<String>,
+    },
+
+    /// List posts newest first as tab-separated id, read state, feed and title
+    ListPosts {
+        /// Only unread posts
+        #[arg(short, long)]
+        unread: bool,
+
+        /// Only posts from this category's feeds
+        #[arg(long, value_name = "NAME")]
+        category: Option<String>,
+
+        /// Print at most this many posts
+        #[arg(short = 'n', long, value_name = "N")]
+        limit: Option<usize>,
+    },
+
+    /// Open a post in the browser by its id and mark it read
+    Open {
+        /// Post id
+        #[arg(value_name = "ID")]
+        id: i64,
+    },
+
+    /// Mark the posts of a feed or category read, or all of them
+    MarkRead {
+        /// Feed URL or category name
+        #[arg(value_name = "FEED_OR_CATEGORY", required_unless_present = "all", conflicts_with = "all")]
+        feed: Option<String>,
+
+        /// Mark every post read
+        #[arg(long)]
+        all: bool,
+    },
+
     /// Clean up old posts (older than specified days)
     Cleanup {
         /// Number of days to keep posts
@@ -94,31 +185,56 @@ pub enum Commands {
     /// Show configuration paths and information
     Info,
 
+    /// Print environment details for bug reports
+    Doctor,
+
+    /// Open the config and data directories in the file manager
+    OpenDirs,
+
     /// List all feeds in the database
     ListFeeds,
 }
 
+/// Environment variable overriding the config directory
+pub const CONFIG_DIR_ENV: &str = "NEWS_CONFIG_DIR";
+/// Environment variable overriding the data (database) directory
+pub const DATA_DIR_ENV: &str = "NEWS_DATA_DIR";
+
+fn env_dir(key: &str) -> Option<PathBuf> {
+    std::env::var_os(key)
+        .filter(|v| !v.is_empty())
+        .map(|v| expand_path(&PathBuf::from(v)))
+}
+
+/// Flag first, then `file_name` inside the env var's directory, then the default
+fn resolve_path(
+    flag: Option<&PathBuf>,
+    env_dir: Option<PathBuf>,
+    file_name: &str,
+    default: impl FnOnce() -> PathBuf,
+) -> PathBuf {
+    match (flag, env_dir) {
+        (Some(path), _) => expand_path(path),
+        (None, Some(dir)) => dir.join(file_name),
+        (None, None) => default(),
+    }
+}
+
 impl Cli {
     pub fn parse_args() -> Self {
         Cli::parse()
     }
 
-    /// Get the config path, using XDG Base Directory if not specified
+    /// Get the config path: `--config` flag, then `$NEWS_CONFIG_DIR`, then XDG default.
+    /// `~` and `$VAR` in the flag or variable are expanded.
     pub fn get_config_path(&self) -> PathBuf {
-        if let Some(ref path) = self.config {
-            path.clone()
-        } else {
-            Self::default_config_path()
-        }
+        resolve_path(self.config.as_ref(), env_dir(CONFIG_DIR_ENV), "config.toml", Self::default_config_path)
     }
 
-    /// Get the database path, using XDG Base Directory if not specified
+    /// Get the database path: `--db-path` flag, then `$NEWS_DATA_DIR`, then XDG default.
+    /// `~` and `$VAR` in the flag or variable are expanded.
     pub fn get_db_path(&self) -> PathBuf {
-        if let Some(ref path) = self.db_path {
-            path.clone()
-        } else {
-            Self::default_db_path()
-        }
+        resolve_path(self.db_path.as_ref(), env_dir(DATA_DIR_ENV), "news_feed.db", Self::default_db_path)
     }
 
     /// Get default config path using XDG Base Directory specification
@@ -145,3 +261,30 @@ impl Cli {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_path() -> PathBuf {
+        PathBuf::from("/xdg/news/news_feed.db")
+    }
+
+    #[test]
+    fn flag_wins_over_env_and_default() {
+        let flag = PathBuf::from("/flag/feeds.db");
+        let path = resolve_path(Some(&flag), Some(PathBuf::from("/sync/news")), "news_feed.db", default_path);
+        assert_eq!(path, flag);
+    }
+
+    #[test]
+    fn env_dir_wins_over_default() {
+        let path = resolve_path(None, Some(PathBuf::from("/sync/news")), "news_feed.db", default_path);
+        assert_eq!(path, PathBuf::from("/sync/news/news_feed.db"));
+    }
+
+    #[test]
+    fn default_is_used_without_flag_or_env() {
+        assert_eq!(resolve_path(None, None, "news_feed.db", default_path), default_path());
+    }
+}
@@ -1,42 +1,291 @@
+use crate::db::normalize_feed_url;
+use crate::export::DigestFormat;
+use crate::keymap::{self, Action};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::error::Error;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
     #[serde(default)]
     pub app: AppConfig,
     #[serde(default)]
     pub ui: UiConfig,
     pub feeds: FeedsConfig,
+    #[serde(default)]
+    pub filters: FiltersConfig,
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    #[serde(default)]
+    pub keybindings: KeyBindingsConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct AppConfig {
     #[serde(default = "default_theme")]
     pub theme: String,
+    /// Delete non-starred posts older than `cleanup_days` on startup
     #[serde(default)]
     pub startup_cleanup: bool,
+    #[serde(default = "default_cleanup_days")]
+    pub cleanup_days: u32,
+    /// Mark the posts shown in a category as read when switching away from it
+    #[serde(default)]
+    pub mark_read_on_category_switch: bool,
+    /// Layout of the share digest: "markdown" or "text"
+    #[serde(default)]
+    pub digest_format: DigestFormat,
+    /// Write the share digest to a file instead of copying it to the clipboard
+    #[serde(default)]
+    pub digest_to_file: bool,
+    /// Open links without bringing the browser to the front, where supported
+    #[serde(default)]
+    pub open_in_background: bool,
+    /// When an opened article counts as read: "open" or "scroll_end"
+    #[serde(default)]
+    pub mark_read_on: MarkReadMode,
+    /// Refresh the active view in the background this often; 0 disables it
+    #[serde(default = "default_refresh_interval_secs")]
+    pub refresh_interval_secs: u64,
+}
+
+/// When a post opened in the article view is marked read
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkReadMode {
+    /// As soon as the article is opened
+    #[default]
+    Open,
+    /// Once the article has been scrolled to (near) its end
+    ScrollEnd,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct UiConfig {
     #[serde(default = "default_true")]
     pub show_ascii_banner: bool,
-    #[serde(default = "default_tab")]
-    pub default_tab: String,
+    /// Smart view to open on startup (`fresh`, `all_feeds`, `starred`, `read_later`
+    /// or `archived`); unset reopens wherever the last session left off
+    #[serde(default)]
+    pub default_tab: Option<String>,
+    /// Treat Read Later as a FIFO queue (oldest saved first); false shows newest first
+    #[serde(default = "default_true")]
+    pub read_later_oldest_first: bool,
+    /// Show the feed's summary instead of its full content when both exist
+    #[serde(default)]
+    pub prefer_summary: bool,
+    /// Use plain-text post badges for terminals without a nerd font
+    #[serde(default)]
+    pub ascii_badges: bool,
+    /// Speed of the article auto-scroll (teleprompter) mode
+    #[serde(default = "default_auto_scroll_lines_per_sec")]
+    pub auto_scroll_lines_per_sec: f64,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct FeedsConfig {
     #[serde(default)]
     pub urls: Vec<String>,
     #[serde(default)]
     pub sources: Vec<FeedSource>,
+    /// Use the feed's own category for feeds added without one
+    #[serde(default)]
+    pub use_feed_category: bool,
+    /// Browser-like user agent for one more try when a feed answers 403 to
+    /// ours, as some Cloudflare-fronted hosts do; empty to turn that off
+    #[serde(default = "default_fallback_user_agent")]
+    pub fallback_user_agent: String,
+    /// Upper bound on article full-text fetches running at once; extra requests wait their turn
+    #[serde(default = "default_max_fulltext_fetches")]
+    pub max_fulltext_fetches: usize,
+    /// Extra attempts for a feed that times out or gets a 5xx, with growing waits between them
+    #[serde(default = "default_fetch_retries")]
+    pub fetch_retries: u32,
+    /// Skip new posts whose title and date match a post from another feed
+    #[serde(default)]
+    pub skip_duplicate_posts: bool,
+}
+
+impl FeedsConfig {
+    /// The fallback user agent, unless it's been turned off
+    pub fn browser_user_agent(&self) -> Option<&str> {
+        Some(self.fallback_user_agent.as_str()).filter(|ua| !ua.is_empty())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct FiltersConfig {
+    /// Posts whose title contains any of these (case-insensitive) are skipped
+    #[serde(default)]
+    pub block: Vec<String>,
+}
+
+/// Palette for `theme = "custom"`, one `#rrggbb` string per theme color.
+/// Colors left out (or that don't parse) come from the Claude Code theme.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mantle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtext: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overlay: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent_primary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent_secondary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surface: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ThemeConfig {
+    pub fn has_colors(&self) -> bool {
+        [
+            &self.base,
+            &self.mantle,
+            &self.text,
+            &self.subtext,
+            &self.overlay,
+            &self.accent_primary,
+            &self.accent_secondary,
+            &self.highlight,
+            &self.surface,
+            &self.warning,
+            &self.success,
+            &self.error,
+        ]
+        .iter()
+        .any(|color| color.is_some())
+    }
+}
+
+/// `[keybindings]`: action name to the key or keys that trigger it. Listed
+/// actions lose their default keys. Unknown actions and keys fail the load.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(try_from = "BTreeMap<String, KeyList>")]
+pub struct KeyBindingsConfig {
+    #[serde(flatten)]
+    pub actions: BTreeMap<String, Vec<String>>,
+}
+
+/// A single key string or a list of them
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl TryFrom<BTreeMap<String, KeyList>> for KeyBindingsConfig {
+    type Error = String;
+
+    fn try_from(table: BTreeMap<String, KeyList>) -> Result<Self, Self::Error> {
+        let mut actions = BTreeMap::new();
+        for (name, keys) in table {
+            if Action::from_name(&name).is_none() {
+                return Err(format!("unknown action '{}' in [keybindings]", name));
+            }
+            let keys = match keys {
+                KeyList::One(key) => vec![key],
+                KeyList::Many(keys) => keys,
+            };
+            for key in &keys {
+                keymap::parse_key(key).map_err(|e| format!("keybindings.{}: {}", name, e))?;
+            }
+            actions.insert(name, keys);
+        }
+        Ok(KeyBindingsConfig { actions })
+    }
+}
+
+impl FiltersConfig {
+    pub fn is_blocked(&self, title: &str) -> bool {
+        if self.block.is_empty() {
+            return false;
+        }
+        let title = title.to_lowercase();
+        self.block
+            .iter()
+            .map(|keyword| keyword.trim().to_lowercase())
+            .any(|keyword| !keyword.is_empty() && title.contains(&keyword))
+    }
+}
+
+/// How many posts each kind of view loads per page; scrolling to the end of
+/// the list loads the next one. 0 loads everything at once.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// Unread posts taken from each category for Fresh
+    #[serde(default = "default_fresh_per_category")]
+    pub fresh_per_category: usize,
+    /// Fresh with read posts shown
+    #[serde(default = "default_view_limit")]
+    pub fresh_all: usize,
+    /// All Feeds view, plus how many posts any one feed may contribute to it
+    #[serde(default = "default_view_limit")]
+    pub all_feeds: usize,
+    #[serde(default = "default_all_feeds_per_feed")]
+    pub all_feeds_per_feed: usize,
+    #[serde(default)]
+    pub starred: usize,
+    #[serde(default)]
+    pub read_later: usize,
+    #[serde(default = "default_view_limit")]
+    pub archived: usize,
+    #[serde(default = "default_view_limit")]
+    pub category: usize,
+    #[serde(default = "default_view_limit")]
+    pub search: usize,
+}
+
+impl LimitsConfig {
+    /// Turn a configured count into a query cap, with 0 meaning unlimited
+    pub fn cap(count: usize) -> Option<usize> {
+        (count > 0).then_some(count)
+    }
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        LimitsConfig {
+            fresh_per_category: default_fresh_per_category(),
+            fresh_all: default_view_limit(),
+            all_feeds: default_view_limit(),
+            all_feeds_per_feed: default_all_feeds_per_feed(),
+            starred: 0,
+            read_later: 0,
+            archived: default_view_limit(),
+            category: default_view_limit(),
+            search: default_view_limit(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct FeedSource {
     #[serde(default)]
     pub url: Option<String>,
@@ -44,9 +293,19 @@ pub struct FeedSource {
     pub urls: Option<Vec<String>>,
     #[serde(default = "default_category")]
     pub category: String,
+    /// Bearer token for private feeds. `$VAR` and `${VAR}` are read from the
+    /// environment so the secret itself can stay out of this file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Skip TLS certificate checks, for self-hosted feeds with self-signed or
+    /// expired certificates. Off unless set explicitly.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_invalid_certs: bool,
 }
 
 impl FeedSource {
+    /// The source's URLs as written. `$VAR` / `${VAR}` in them are stored
+    /// unexpanded and only filled in when the feed is fetched.
     pub fn get_urls(&self) -> Vec<String> {
         let mut result = Vec::new();
         if let Some(ref url) = self.url {
@@ -59,27 +318,141 @@ impl FeedSource {
     }
 }
 
+impl FeedsConfig {
+    fn source_for(&self, url: &str) -> Option<&FeedSource> {
+        let url = normalize_feed_url(url);
+        self.sources
+            .iter()
+            .find(|source| source.get_urls().iter().any(|u| normalize_feed_url(u) == url))
+    }
+
+    /// Whether the source listing `url` opted out of certificate checks
+    pub fn allows_invalid_certs(&self, url: &str) -> bool {
+        self.source_for(url).is_some_and(|source| source.allow_invalid_certs)
+    }
+
+    /// The expanded token of the source that lists `url`, if it has one
+    pub fn token_for(&self, url: &str) -> Result<Option<String>, String> {
+        match self.source_for(url).and_then(|s| s.token.as_deref()) {
+            Some(token) => expand_env_vars(token).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Replace `$VAR` and `${VAR}` with values from the environment. `$$` is a
+/// literal `$`. Unset variables are an error rather than an empty string, so a
+/// missing secret shows up as a feed error instead of a confusing 401.
+pub fn expand_env_vars(value: &str) -> Result<String, String> {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            result.push(c);
+            continue;
+        }
+
+        let name: String = match chars.peek() {
+            Some('$') => {
+                chars.next();
+                result.push('$');
+                continue;
+            }
+            Some('{') => {
+                chars.next();
+                let name: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                if name.is_empty() {
+                    return Err("empty ${} in token".to_string());
+                }
+                name
+            }
+            _ => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        name.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if name.is_empty() {
+                    result.push('$');
+                    continue;
+                }
+                name
+            }
+        };
+
+        let value = std::env::var(&name)
+            .map_err(|_| format!("environment variable {} is not set", name))?;
+        result.push_str(&value);
+    }
+
+    Ok(result)
+}
+
 fn default_theme() -> String {
     "catppuccin-mocha".to_string()
 }
 
-fn default_true() -> bool {
-    true
+fn default_cleanup_days() -> u32 {
+    30
+}
+
+fn default_refresh_interval_secs() -> u64 {
+    30 * 60
+}
+
+fn default_max_fulltext_fetches() -> usize {
+    3
+}
+
+fn default_fetch_retries() -> u32 {
+    3
+}
+
+fn default_auto_scroll_lines_per_sec() -> f64 {
+    1.5
 }
 
-fn default_tab() -> String {
-    "all-posts".to_string()
+fn default_fresh_per_category() -> usize {
+    15
+}
+
+fn default_all_feeds_per_feed() -> usize {
+    10
+}
+
+fn default_view_limit() -> usize {
+    100
+}
+
+fn default_true() -> bool {
+    true
 }
 
 fn default_category() -> String {
     "General".to_string()
 }
 
+fn default_fallback_user_agent() -> String {
+    "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0".to_string()
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         AppConfig {
             theme: default_theme(),
             startup_cleanup: false,
+            cleanup_days: default_cleanup_days(),
+            mark_read_on_category_switch: false,
+            digest_format: DigestFormat::default(),
+            digest_to_file: false,
+            open_in_background: false,
+            mark_read_on: MarkReadMode::default(),
+            refresh_interval_secs: default_refresh_interval_secs(),
         }
     }
 }
@@ -88,7 +461,11 @@ impl Default for UiConfig {
     fn default() -> Self {
         UiConfig {
             show_ascii_banner: true,
-            default_tab: default_tab(),
+            default_tab: None,
+            read_later_oldest_first: true,
+            prefer_summary: false,
+            ascii_badges: false,
+            auto_scroll_lines_per_sec: default_auto_scroll_lines_per_sec(),
         }
     }
 }
@@ -96,21 +473,48 @@ impl Default for UiConfig {
 impl Default for FeedsConfig {
     fn default() -> Self {
         FeedsConfig {
-            urls: vec![],
-            sources: vec![],
+            urls: Vec::new(),
+            sources: Vec::new(),
+            use_feed_category: false,
+            fallback_user_agent: default_fallback_user_agent(),
+            max_fulltext_fetches: default_max_fulltext_fetches(),
+            fetch_retries: default_fetch_retries(),
+            skip_duplicate_posts: false,
         }
     }
 }
 
-pub fn load_config_from_path<P: AsRef<Path>>(path: P) -> Result<Config, Box<dyn Error>> {
-    let path = path.as_ref();
+/// Expand a leading `~` and `$VAR` / `${VAR}` in a path, for paths quoted
+/// past the shell, set through `NEWS_CONFIG_DIR` / `NEWS_DATA_DIR`, or kept
+/// in shared dotfiles. With a variable unset the rest is left as written.
+pub fn expand_path(path: &Path) -> PathBuf {
+    let Some(text) = path.to_str() else {
+        return path.to_path_buf();
+    };
+    let home = directories::BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+    let text = match (text.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            format!("{}{}", home.display(), rest)
+        }
+        _ => text.to_string(),
+    };
+    PathBuf::from(expand_env_vars(&text).unwrap_or(text))
+}
+
+/// Load the config, creating a default one if the file doesn't exist. Keys
+/// the app doesn't know, e.g. retired ones or typos, are skipped rather than
+/// failing the load, and come back as a note naming them for the status bar.
+pub fn load_config_from_path<P: AsRef<Path>>(path: P) -> Result<(Config, Option<String>), Box<dyn Error>> {
+    let path = &expand_path(path.as_ref());
 
     // Try to read existing config
     match fs::read_to_string(path) {
         Ok(config_str) => {
-            let config: Config = toml::from_str(&config_str)
-                .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
-            Ok(config)
+            let (config, unknown) = parse_config(&config_str)
+                .map_err(|e| format!("Failed to parse {}{}", path.display(), describe_toml_error(&config_str, &e)))?;
+            let note = (!unknown.is_empty())
+                .then(|| format!("Ignored unknown keys in {}: {}", path.display(), unknown.join(", ")));
+            Ok((config, note))
         }
         Err(_) => {
             // Create default config if it doesn't exist
@@ -118,25 +522,40 @@ pub fn load_config_from_path<P: AsRef<Path>>(path: P) -> Result<Config, Box<dyn
                 app: AppConfig::default(),
                 ui: UiConfig::default(),
                 feeds: FeedsConfig {
+                    use_feed_category: false,
+                    fallback_user_agent: default_fallback_user_agent(),
+                    max_fulltext_fetches: default_max_fulltext_fetches(),
+                    fetch_retries: default_fetch_retries(),
+                    skip_duplicate_posts: false,
                     urls: vec![],
                     sources: vec![
                         FeedSource {
                             url: Some("https://nesslabs.com/feed".to_string()),
                             urls: None,
                             category: "Productivity".to_string(),
+                            token: None,
+                            allow_invalid_certs: false,
                         },
                         FeedSource {
                             url: Some("https://dev.to/rss".to_string()),
                             urls: None,
                             category: "Technology".to_string(),
+                            token: None,
+                            allow_invalid_certs: false,
                         },
                         FeedSource {
                             url: Some("https://jamesclear.com/feed".to_string()),
                             urls: None,
                             category: "Productivity".to_string(),
+                            token: None,
+                            allow_invalid_certs: false,
                         },
                     ],
                 },
+                filters: FiltersConfig::default(),
+                limits: LimitsConfig::default(),
+                theme: ThemeConfig::default(),
+                keybindings: KeyBindingsConfig::default(),
             };
 
             // Ensure parent directory exists
@@ -146,8 +565,9 @@ pub fn load_config_from_path<P: AsRef<Path>>(path: P) -> Result<Config, Box<dyn
 
             // Save the default config
             save_config_to_path(&default_config, path)?;
+            append_keybindings_reference(path)?;
             eprintln!("Created default config at: {}", path.display());
-            Ok(default_config)
+            Ok((default_config, None))
         }
     }
 }
@@ -172,3 +592,299 @@ pub fn save_config_to_path<P: AsRef<Path>>(config: &Config, path: P) -> Result<(
         .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
     Ok(())
 }
+
+/// Set `[app] theme` in the config file, leaving every other line, comments
+/// included, as the user wrote it
+pub fn save_theme_to_path(theme: &str, path: &Path) -> Result<(), Box<dyn Error>> {
+    let source = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut document = source
+        .parse::<toml_edit::DocumentMut>()
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+    let app = document
+        .entry("app")
+        .or_insert_with(toml_edit::table)
+        .as_table_like_mut()
+        .ok_or("[app] is not a table")?;
+    match app.get_mut("theme").and_then(|item| item.as_value_mut()) {
+        // Replacing the value in place keeps any comment trailing it
+        Some(value) => {
+            let decor = value.decor().clone();
+            *value = theme.into();
+            *value.decor_mut() = decor;
+        }
+        None => {
+            app.insert("theme", toml_edit::value(theme));
+        }
+    }
+
+    fs::write(path, document.to_string())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(())
+}
+
+/// Add the commented list of bindable actions to a freshly written config,
+/// under its (empty) `[keybindings]` table
+fn append_keybindings_reference(path: &Path) -> Result<(), Box<dyn Error>> {
+    use std::io::Write;
+
+    let mut file = fs::OpenOptions::new()
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    file.write_all(keymap::reference_comment().as_bytes())?;
+    Ok(())
+}
+
+/// Deserialize a config, dropping each key serde rejects as an unknown field
+/// and trying again. Returns the config and the dropped keys as
+/// `ui.old_option (line 4)`; any other error fails as before.
+fn parse_config(source: &str) -> Result<(Config, Vec<String>), toml::de::Error> {
+    use serde::Deserialize;
+
+    let mut root = toml::de::DeTable::parse(source)?;
+    let mut unknown = Vec::new();
+    loop {
+        let error = match Config::deserialize(toml::de::Deserializer::from(root.clone())) {
+            Ok(config) => return Ok((config, unknown)),
+            Err(error) => error,
+        };
+        let removed = error
+            .message()
+            .starts_with("unknown field")
+            .then(|| error.span())
+            .flatten()
+            .and_then(|span| remove_key_at(root.get_mut(), &span, ""));
+        match removed {
+            Some(key) => {
+                let line = source[..error.span().map_or(0, |span| span.start)].matches('\n').count() + 1;
+                unknown.push(format!("{} (line {})", key, line));
+            }
+            None => return Err(error),
+        }
+    }
+}
+
+/// Remove the key written at `span` from `table` or any table nested in it,
+/// returning its dotted path
+fn remove_key_at(table: &mut toml::de::DeTable, span: &std::ops::Range<usize>, prefix: &str) -> Option<String> {
+    let join = |key: &str| if prefix.is_empty() { key.to_string() } else { format!("{}.{}", prefix, key) };
+
+    let found = table.iter().map(|(key, _)| key).find(|key| key.span() == *span).cloned();
+    if let Some(key) = found {
+        table.remove(key.get_ref().as_ref());
+        return Some(join(key.get_ref()));
+    }
+    table.iter_mut().find_map(|(key, value)| {
+        let path = join(key.get_ref());
+        match value.get_mut() {
+            toml::de::DeValue::Table(nested) => remove_key_at(nested, span, &path),
+            toml::de::DeValue::Array(items) => items.iter_mut().find_map(|item| match item.get_mut() {
+                toml::de::DeValue::Table(nested) => remove_key_at(nested, span, &path),
+                _ => None,
+            }),
+            _ => None,
+        }
+    })
+}
+
+/// The parse error on one line, as ` at line 3, column 16: invalid type: ...`.
+/// toml's own Display spreads the position, a source excerpt and the message
+/// over several lines, which doesn't fit the status bar.
+fn describe_toml_error(source: &str, error: &toml::de::Error) -> String {
+    let message = error.message().trim().replace('\n', " ");
+    match error.span() {
+        Some(span) => {
+            let before = &source[..span.start.min(source.len())];
+            let line = before.matches('\n').count() + 1;
+            let column = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+            format!(" at line {}, column {}: {}", line, column, message)
+        }
+        None => format!(": {}", message),
+    }
+}
+
+/// Fallback for a config file that exists but won't parse. The broken file is
+/// copied next to itself as `config.toml.bak`, and each top-level section that
+/// still deserializes on its own is kept so a typo in one table doesn't drop
+/// the user's feeds. Returns the salvaged config and a note for the user.
+pub fn recover_config(path: &Path, error: &dyn Error) -> (Config, String) {
+    let mut backup_name = path.file_name().unwrap_or_default().to_os_string();
+    backup_name.push(".bak");
+    let backup_path: PathBuf = path.with_file_name(backup_name);
+    let backed_up = fs::copy(path, &backup_path).is_ok();
+
+    let table = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .unwrap_or_default();
+
+    fn section<T: serde::de::DeserializeOwned + Default>(table: &toml::Table, key: &str, kept: &mut Vec<String>) -> T {
+        match table.get(key).cloned().map(|value| value.try_into::<T>()) {
+            Some(Ok(value)) => {
+                kept.push(format!("[{}]", key));
+                value
+            }
+            _ => T::default(),
+        }
+    }
+
+    let mut kept = Vec::new();
+    let config = Config {
+        app: section(&table, "app", &mut kept),
+        ui: section(&table, "ui", &mut kept),
+        feeds: section(&table, "feeds", &mut kept),
+        filters: section(&table, "filters", &mut kept),
+        limits: section(&table, "limits", &mut kept),
+        theme: section(&table, "theme", &mut kept),
+        keybindings: section(&table, "keybindings", &mut kept),
+    };
+
+    // toml errors carry a multi-line source excerpt; the first line is enough for the status bar
+    let error = error.to_string();
+    let mut note = format!("Config error: {}.", error.lines().next().unwrap_or_default());
+    if backed_up {
+        note.push_str(&format!(" Original saved to {}.", backup_path.display()));
+    }
+    if kept.is_empty() {
+        note.push_str(" Using defaults.");
+    } else {
+        note.push_str(&format!(" Kept {}; other sections use defaults.", kept.join(", ")));
+    }
+    // The file is left as it is, so fixing it and reloading brings the user's settings back
+    note.push_str(" Fix the file and press R to reload it.");
+    (config, note)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A config file in the temp dir, removed when dropped
+    struct TempConfig(PathBuf);
+
+    impl TempConfig {
+        fn new(name: &str, content: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("news-feed-{}-{}.toml", name, std::process::id()));
+            fs::write(&path, content).unwrap();
+            TempConfig(path)
+        }
+
+        fn read(&self) -> String {
+            fs::read_to_string(&self.0).unwrap()
+        }
+    }
+
+    impl Drop for TempConfig {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn save_theme_keeps_comments_and_other_keys() {
+        let file = TempConfig::new(
+            "theme-keep",
+            "# my settings\n[app]\ntheme = \"nord\"  # favourite\nrefresh_interval = 30\n\n[keybindings]\n# quit = [\"q\"]\n",
+        );
+        save_theme_to_path("dracula", &file.0).unwrap();
+        assert_eq!(
+            file.read(),
+            "# my settings\n[app]\ntheme = \"dracula\"  # favourite\nrefresh_interval = 30\n\n[keybindings]\n# quit = [\"q\"]\n",
+        );
+    }
+
+    #[test]
+    fn save_theme_adds_a_missing_key() {
+        let file = TempConfig::new("theme-add", "# empty app table\n[app]\n");
+        save_theme_to_path("gruvbox", &file.0).unwrap();
+        let config: toml::Table = file.read().parse().unwrap();
+        assert_eq!(config["app"]["theme"].as_str(), Some("gruvbox"));
+        assert!(file.read().starts_with("# empty app table\n"));
+    }
+
+    #[test]
+    fn blocklist_matches_titles_case_insensitively() {
+        let filters = FiltersConfig {
+            block: vec!["Sponsored".to_string(), "  advertisement ".to_string(), " ".to_string()],
+        };
+        assert!(filters.is_blocked("SPONSORED: a new laptop"));
+        assert!(filters.is_blocked("This post is sponsored"));
+        assert!(filters.is_blocked("An Advertisement for nothing"));
+        assert!(!filters.is_blocked("Rust 1.90 released"));
+        assert!(!FiltersConfig::default().is_blocked("Sponsored"));
+    }
+
+    #[test]
+    fn expands_dollar_and_braced_variables() {
+        // PATH is set wherever the tests run, so nothing has to touch the environment
+        let path = std::env::var("PATH").unwrap();
+        assert_eq!(expand_env_vars("Bearer $PATH").unwrap(), format!("Bearer {}", path));
+        assert_eq!(expand_env_vars("${PATH}-x").unwrap(), format!("{}-x", path));
+        assert_eq!(expand_env_vars("cost: $$5, $ alone").unwrap(), "cost: $5, $ alone");
+        assert_eq!(expand_env_vars("no variables").unwrap(), "no variables");
+    }
+
+    #[test]
+    fn missing_variable_is_an_error() {
+        assert_eq!(
+            expand_env_vars("$NEWS_FEED_TEST_UNSET_TOKEN").unwrap_err(),
+            "environment variable NEWS_FEED_TEST_UNSET_TOKEN is not set"
+        );
+        assert!(expand_env_vars("${}").is_err());
+    }
+
+    #[test]
+    fn bundled_config_loads() {
+        toml::from_str::<Config>(include_str!("../config.toml")).unwrap();
+    }
+
+    #[test]
+    fn unknown_keys_are_skipped_and_named() {
+        let file = TempConfig::new(
+            "typo-key",
+            "[app]\ntheme = \"nord\"\nstartup_cleanp = true\n\n[feeds]\nurls = []\n\n\
+             [[feeds.sources]]\nurl = \"https://example.com/feed\"\ncolour = \"red\"\n",
+        );
+        let (config, note) = load_config_from_path(&file.0).unwrap();
+        assert_eq!(config.app.theme, "nord");
+        assert_eq!(config.feeds.sources[0].url.as_deref(), Some("https://example.com/feed"));
+        let note = note.unwrap();
+        assert!(note.contains("app.startup_cleanp (line 3)"), "{}", note);
+        assert!(note.contains("feeds.sources.colour (line 10)"), "{}", note);
+    }
+
+    #[test]
+    fn known_keys_load_without_a_note() {
+        let file = TempConfig::new("known-keys", "[app]\ntheme = \"nord\"\n\n[feeds]\nurls = []\n");
+        assert!(load_config_from_path(&file.0).unwrap().1.is_none());
+    }
+
+    #[test]
+    fn malformed_value_names_its_line_and_column() {
+        let file = TempConfig::new("bad-value", "[feeds]\nurls = []\n\n[app]\ncleanup_days = \"thirty\"\n");
+        let error = load_config_from_path(&file.0).unwrap_err().to_string();
+        assert!(error.contains("at line 5, column 16:"), "{}", error);
+        assert!(error.contains("invalid type"), "{}", error);
+    }
+
+    #[test]
+    fn source_urls_stay_templates_and_still_find_their_token() {
+        let source = FeedSource {
+            url: Some("https://example.com/feed?key=${FEED_KEY}".to_string()),
+            urls: None,
+            category: "Work".to_string(),
+            token: Some("$$literal".to_string()),
+            allow_invalid_certs: true,
+        };
+        let feeds = FeedsConfig {
+            sources: vec![source.clone()],
+            ..Default::default()
+        };
+
+        assert_eq!(source.get_urls(), vec!["https://example.com/feed?key=${FEED_KEY}"]);
+        assert_eq!(feeds.token_for("https://example.com/feed?key=${FEED_KEY}").unwrap().as_deref(), Some("$literal"));
+        assert!(feeds.allows_invalid_certs("https://example.com/feed?key=${FEED_KEY}"));
+    }
+}
@@ -1,23 +1,63 @@
+use rusqlite::functions::FunctionFlags;
 use rusqlite::{params, Connection, Result};
 use std::error::Error;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::reading;
+use crate::rss::CacheHeaders;
+
+/// Bucket for feeds added through quick paths (config URL lists, bare imports)
+/// that never had a category chosen. "General" stays the user's deliberate default.
+pub const UNCATEGORIZED: &str = "Uncategorized";
 
 pub struct Database {
     conn: Connection,
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Feed {
     pub id: i64,
     pub url: String,
     pub title: Option<String>,
     pub category: String,
+    pub last_fetched_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    /// Fetches that have failed in a row since the last success
+    #[serde(default)]
+    pub consecutive_failures: u32,
+    /// Added without an explicit category, so the feed's own category may replace it
+    pub auto_category: bool,
+    pub description: Option<String>,
+}
+
+const FEED_COLUMNS: &str =
+    "id, url, title, COALESCE(category, 'General'), last_fetched_at, last_error, auto_category, description,
+     consecutive_failures";
+
+fn feed_from_row(row: &rusqlite::Row) -> Result<Feed> {
+    Ok(Feed {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        title: row.get(2)?,
+        category: row.get(3)?,
+        last_fetched_at: parse_timestamp(row.get(4)?),
+        last_error: row.get(5)?,
+        auto_category: row.get(6)?,
+        description: row.get(7)?,
+        consecutive_failures: row.get(8)?,
+    })
+}
+
+fn parse_timestamp(value: Option<String>) -> Option<DateTime<Utc>> {
+    value.and_then(|s| DateTime::parse_from_rfc3339(&s).ok().map(|d| d.with_timezone(&Utc)))
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Post {
     pub id: i64,
     pub feed_id: i64,
@@ -30,6 +70,48 @@ pub struct Post {
     pub is_archived: bool,
     pub is_read_later: bool,
     pub feed_title: Option<String>,
+    pub read_later_at: Option<DateTime<Utc>>,
+    /// Position in the Read Later queue, lowest first
+    pub read_later_order: Option<i64>,
+    /// Feed-provided summary, kept alongside the full content
+    pub summary: Option<String>,
+    /// First audio or video attachment, e.g. a podcast episode
+    pub enclosure_url: Option<String>,
+    /// Estimated from the content when loaded; `None` for posts without text
+    #[serde(skip)]
+    pub reading_minutes: Option<u32>,
+}
+
+const POST_COLUMNS: &str = "p.id, p.feed_id, p.title, p.url, p.content, p.pub_date, p.is_read, p.is_bookmarked,
+    COALESCE(p.is_archived, 0), COALESCE(p.is_read_later, 0), f.title, p.read_later_at, p.read_later_order, p.summary,
+    p.enclosure_url";
+
+fn post_from_row(row: &rusqlite::Row) -> Result<Post> {
+    let content: Option<String> = row.get(4)?;
+    let summary: Option<String> = row.get(13)?;
+    let reading_minutes = content
+        .as_deref()
+        .or(summary.as_deref())
+        .and_then(reading::reading_minutes);
+
+    Ok(Post {
+        id: row.get(0)?,
+        feed_id: row.get(1)?,
+        title: row.get(2)?,
+        url: row.get(3)?,
+        content,
+        pub_date: parse_timestamp(row.get(5)?),
+        is_read: row.get(6)?,
+        is_bookmarked: row.get(7)?,
+        is_archived: row.get(8)?,
+        is_read_later: row.get(9)?,
+        feed_title: row.get(10)?,
+        read_later_at: parse_timestamp(row.get(11)?),
+        read_later_order: row.get(12)?,
+        summary,
+        enclosure_url: row.get(14)?,
+        reading_minutes,
+    })
 }
 
 #[allow(dead_code)]
@@ -47,7 +129,8 @@ impl Database {
         }
 
         let conn = Connection::open(path)?;
-        
+        register_functions(&conn)?;
+
         conn.execute(
             "CREATE TABLE IF NOT EXISTS feeds (
                 id INTEGER PRIMARY KEY,
@@ -85,9 +168,10 @@ impl Database {
     }
 
     pub fn add_feed(&self, url: &str) -> Result<i64> {
+        let url = normalize_feed_url(url);
         self.conn.execute(
-            "INSERT OR IGNORE INTO feeds (url) VALUES (?1)",
-            params![url],
+            "INSERT OR IGNORE INTO feeds (url, category, auto_category) VALUES (?1, ?2, 1)",
+            params![url, UNCATEGORIZED],
         )?;
         let id: i64 = self.conn.query_row(
             "SELECT id FROM feeds WHERE url = ?1",
@@ -98,15 +182,8 @@ impl Database {
     }
 
     pub fn get_feeds(&self) -> Result<Vec<Feed>> {
-        let mut stmt = self.conn.prepare("SELECT id, url, title, COALESCE(category, 'General') FROM feeds")?;
-        let feed_iter = stmt.query_map([], |row| {
-            Ok(Feed {
-                id: row.get(0)?,
-                url: row.get(1)?,
-                title: row.get(2)?,
-                category: row.get(3)?,
-            })
-        })?;
+        let mut stmt = self.conn.prepare(&format!("SELECT {} FROM feeds", FEED_COLUMNS))?;
+        let feed_iter = stmt.query_map([], feed_from_row)?;
 
         let mut feeds = Vec::new();
         for feed in feed_iter {
@@ -115,21 +192,71 @@ impl Database {
         Ok(feeds)
     }
 
-    pub fn insert_post(&self, feed_id: i64, title: &str, url: &str, content: Option<&str>, pub_date: Option<DateTime<Utc>>) -> Result<()> {
+    /// Insert a post, returning `false` when it was ignored as a duplicate
+    pub fn insert_post(
+        &self,
+        feed_id: i64,
+        title: &str,
+        url: &str,
+        content: Option<&str>,
+        summary: Option<&str>,
+        pub_date: Option<DateTime<Utc>>,
+    ) -> Result<bool> {
         let pub_date_str = pub_date.map(|d| d.to_rfc3339());
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO posts (feed_id, title, url, content, summary, pub_date) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![feed_id, title, url, content, summary, pub_date_str],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Attach an audio/video URL to the post at `url` unless it already has
+    /// one, so posts stored before enclosures were kept pick theirs up too
+    pub fn set_post_enclosure_if_missing(&self, url: &str, enclosure_url: &str) -> Result<()> {
         self.conn.execute(
-            "INSERT OR IGNORE INTO posts (feed_id, title, url, content, pub_date) VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![feed_id, title, url, content, pub_date_str],
+            "UPDATE posts SET enclosure_url = ?1 WHERE url = ?2 AND enclosure_url IS NULL",
+            params![enclosure_url, url],
         )?;
         Ok(())
     }
 
+    /// Whether a post with the same normalized title was published within
+    /// half a day of `pub_date`, in any feed. Catches one article syndicated
+    /// under different URLs; posts without a date are never matched.
+    pub fn post_exists_similar(&self, title: &str, pub_date: Option<DateTime<Utc>>) -> Result<bool> {
+        let Some(pub_date) = pub_date else {
+            return Ok(false);
+        };
+        let wanted = normalize_title(title);
+        if wanted.is_empty() {
+            return Ok(false);
+        }
+
+        let window = chrono::Duration::hours(12);
+        let mut stmt = self
+            .conn
+            .prepare("SELECT title FROM posts WHERE pub_date BETWEEN ?1 AND ?2")?;
+        let titles = stmt.query_map(
+            params![(pub_date - window).to_rfc3339(), (pub_date + window).to_rfc3339()],
+            |row| row.get::<_, String>(0),
+        )?;
+        for candidate in titles {
+            if normalize_title(&candidate?) == wanted {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
     pub fn get_posts(&self, filter: PostFilter) -> Result<Vec<Post>> {
-        let mut query = "SELECT p.id, p.feed_id, p.title, p.url, p.content, p.pub_date, p.is_read, p.is_bookmarked, COALESCE(p.is_archived, 0), COALESCE(p.is_read_later, 0), f.title
-                         FROM posts p
-                         JOIN feeds f ON p.feed_id = f.id".to_string();
+        let mut query = format!(
+            "SELECT {}
+             FROM posts p
+             JOIN feeds f ON p.feed_id = f.id",
+            POST_COLUMNS
+        );
 
-        let mut conditions = Vec::new();
+        let mut conditions = vec!["(?3 IS NULL OR f.category = ?3)"];
         if filter.only_unread {
             conditions.push("p.is_read = 0");
         }
@@ -143,32 +270,15 @@ impl Database {
             conditions.push("p.is_read_later = 1");
         }
 
-        if !conditions.is_empty() {
-            query.push_str(" WHERE ");
-            query.push_str(&conditions.join(" AND "));
-        }
-
-        query.push_str(" ORDER BY p.pub_date DESC LIMIT 100"); // Limit for MVP
+        query.push_str(" WHERE ");
+        query.push_str(&conditions.join(" AND "));
+        query.push_str(" ORDER BY p.pub_date DESC LIMIT ?1 OFFSET ?2");
 
         let mut stmt = self.conn.prepare(&query)?;
-        let post_iter = stmt.query_map([], |row| {
-            let pub_date_str: Option<String> = row.get(5)?;
-            let pub_date = pub_date_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok().map(|d| d.with_timezone(&Utc)));
-
-            Ok(Post {
-                id: row.get(0)?,
-                feed_id: row.get(1)?,
-                title: row.get(2)?,
-                url: row.get(3)?,
-                content: row.get(4)?,
-                pub_date,
-                is_read: row.get(6)?,
-                is_bookmarked: row.get(7)?,
-                is_archived: row.get(8)?,
-                is_read_later: row.get(9)?,
-                feed_title: row.get(10)?,
-            })
-        })?;
+        let post_iter = stmt.query_map(
+            params![sql_limit(filter.limit), filter.offset as i64, filter.category],
+            post_from_row,
+        )?;
 
         let mut posts = Vec::new();
         for post in post_iter {
@@ -185,6 +295,53 @@ impl Database {
         Ok(())
     }
 
+    pub fn mark_posts_read(&self, post_ids: &[i64]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for post_id in post_ids {
+            tx.execute(
+                "UPDATE posts SET is_read = 1 WHERE id = ?1",
+                params![post_id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Mark every unread post of a feed read, returning how many changed
+    pub fn mark_feed_read(&self, feed_id: i64) -> Result<usize> {
+        self.conn.execute(
+            "UPDATE posts SET is_read = 1 WHERE feed_id = ?1 AND is_read = 0",
+            params![feed_id],
+        )
+    }
+
+    /// Mark every unread post of a category's feeds read, returning how many changed
+    pub fn mark_category_read(&self, category: &str) -> Result<usize> {
+        self.conn.execute(
+            "UPDATE posts SET is_read = 1
+             WHERE is_read = 0 AND feed_id IN (SELECT id FROM feeds WHERE category = ?1)",
+            params![category],
+        )
+    }
+
+    /// Mark every unread post read, returning how many changed
+    pub fn mark_all_read(&self) -> Result<usize> {
+        self.conn.execute("UPDATE posts SET is_read = 1 WHERE is_read = 0", [])
+    }
+
+    /// Renumber the Read Later queue so `post_ids` come first, in that order
+    pub fn set_read_later_order(&self, post_ids: &[i64]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for (position, post_id) in post_ids.iter().enumerate() {
+            tx.execute(
+                "UPDATE posts SET read_later_order = ?1 WHERE id = ?2",
+                params![position as i64 + 1, post_id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     pub fn mark_as_unread(&self, post_id: i64) -> Result<()> {
         self.conn.execute(
             "UPDATE posts SET is_read = 0 WHERE id = ?1",
@@ -217,6 +374,17 @@ impl Database {
         Ok(())
     }
 
+    /// Delete every post in a category, optionally sparing bookmarked ones. Feeds are kept.
+    pub fn delete_posts_by_category(&self, name: &str, keep_bookmarked: bool) -> Result<usize> {
+        let deleted = self.conn.execute(
+            "DELETE FROM posts
+             WHERE feed_id IN (SELECT id FROM feeds WHERE category = ?1)
+               AND (?2 = 0 OR is_bookmarked = 0)",
+            params![name, keep_bookmarked],
+        )?;
+        Ok(deleted)
+    }
+
     fn migrate_schema(&self) -> Result<()> {
         // Check and add new columns to posts table if they don't exist
         let has_is_archived = self.conn.query_row(
@@ -272,9 +440,209 @@ impl Database {
             )?;
         }
 
+        // Feed health tracking
+        self.add_column_if_missing("feeds", "last_fetched_at", "TEXT")?;
+        self.add_column_if_missing("feeds", "last_error", "TEXT")?;
+        self.add_column_if_missing("feeds", "consecutive_failures", "INTEGER NOT NULL DEFAULT 0")?;
+        self.add_column_if_missing("feeds", "auto_category", "BOOLEAN NOT NULL DEFAULT 0")?;
+        self.add_column_if_missing("posts", "read_later_at", "TEXT")?;
+        self.add_column_if_missing("feeds", "description", "TEXT")?;
+        self.add_column_if_missing("posts", "summary", "TEXT")?;
+        self.add_column_if_missing("feeds", "etag", "TEXT")?;
+        self.add_column_if_missing("feeds", "last_modified", "TEXT")?;
+        self.add_column_if_missing("posts", "enclosure_url", "TEXT")?;
+        self.add_column_if_missing("feeds", "last_checked_at", "TEXT")?;
+        if self.add_column_if_missing("posts", "read_later_order", "INTEGER")? {
+            // Seed the queue from save times so existing Read Later items keep their order
+            self.conn.execute(
+                "UPDATE posts SET read_later_order = (
+                     SELECT COUNT(*) FROM posts p2
+                     WHERE p2.is_read_later = 1
+                       AND (COALESCE(p2.read_later_at, '') < COALESCE(posts.read_later_at, '')
+                            OR (COALESCE(p2.read_later_at, '') = COALESCE(posts.read_later_at, '')
+                                AND p2.id <= posts.id))
+                 )
+                 WHERE is_read_later = 1",
+                [],
+            )?;
+        }
+        self.normalize_stored_feed_urls()?;
+
+        Ok(())
+    }
+
+    /// Rewrite feed URLs saved before `normalize_feed_url` existed so lookups
+    /// by URL find them. A feed whose normalized URL is already subscribed is
+    /// merged into that subscription: its posts move over and the row goes.
+    fn normalize_stored_feed_urls(&self) -> Result<()> {
+        let feeds: Vec<(i64, String)> = {
+            let mut stmt = self.conn.prepare("SELECT id, url FROM feeds ORDER BY id")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<Result<_>>()?
+        };
+
+        for (id, url) in feeds {
+            let normalized = normalize_feed_url(&url);
+            if normalized == url {
+                continue;
+            }
+            let existing: Option<i64> = {
+                let mut stmt = self.conn.prepare("SELECT id FROM feeds WHERE url = ?1")?;
+                let mut rows = stmt.query_map(params![normalized], |row| row.get(0))?;
+                rows.next().transpose()?
+            };
+            match existing {
+                Some(keep) => {
+                    let tx = self.conn.unchecked_transaction()?;
+                    tx.execute("UPDATE posts SET feed_id = ?1 WHERE feed_id = ?2", params![keep, id])?;
+                    tx.execute(
+                        "UPDATE feeds SET title = COALESCE(title, (SELECT title FROM feeds WHERE id = ?2))
+                         WHERE id = ?1",
+                        params![keep, id],
+                    )?;
+                    tx.execute("DELETE FROM feeds WHERE id = ?1", params![id])?;
+                    tx.commit()?;
+                }
+                None => {
+                    self.conn.execute("UPDATE feeds SET url = ?1 WHERE id = ?2", params![normalized, id])?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` when the column had to be added
+    fn add_column_if_missing(&self, table: &str, column: &str, definition: &str) -> Result<bool> {
+        let exists = self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM pragma_table_info('{}') WHERE name = ?1", table),
+            params![column],
+            |row| row.get::<_, i64>(0),
+        )? > 0;
+
+        if !exists {
+            self.conn.execute(
+                &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+                [],
+            )?;
+        }
+        Ok(!exists)
+    }
+
+    /// Record the outcome of fetching a feed. Success stamps the fetch time and
+    /// clears the error and failure streak; failure stores the error and
+    /// extends the streak.
+    pub fn record_feed_result(&self, feed_id: i64, result: std::result::Result<(), &str>) -> Result<()> {
+        match result {
+            Ok(()) => self.conn.execute(
+                "UPDATE feeds SET last_fetched_at = ?1, last_error = NULL, consecutive_failures = 0 WHERE id = ?2",
+                params![Utc::now().to_rfc3339(), feed_id],
+            ),
+            Err(error) => self.conn.execute(
+                "UPDATE feeds SET last_error = ?1, consecutive_failures = consecutive_failures + 1 WHERE id = ?2",
+                params![error, feed_id],
+            ),
+        }?;
+        Ok(())
+    }
+
+    /// Record the outcome of a health check. The feed's error and failure
+    /// streak are updated as for a fetch, but only the check time is stamped:
+    /// no posts were stored, so `last_fetched_at` stays put.
+    pub fn record_feed_check(&self, feed_id: i64, result: std::result::Result<(), &str>) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        match result {
+            Ok(()) => self.conn.execute(
+                "UPDATE feeds SET last_checked_at = ?1, last_error = NULL, consecutive_failures = 0 WHERE id = ?2",
+                params![now, feed_id],
+            ),
+            Err(error) => self.conn.execute(
+                "UPDATE feeds SET last_checked_at = ?1, last_error = ?2, consecutive_failures = consecutive_failures + 1
+                 WHERE id = ?3",
+                params![now, error, feed_id],
+            ),
+        }?;
+        Ok(())
+    }
+
+    pub fn get_feed_cache_headers(&self, feed_id: i64) -> Result<CacheHeaders> {
+        self.conn.query_row(
+            "SELECT etag, last_modified FROM feeds WHERE id = ?1",
+            params![feed_id],
+            |row| {
+                Ok(CacheHeaders {
+                    etag: row.get(0)?,
+                    last_modified: row.get(1)?,
+                })
+            },
+        )
+    }
+
+    pub fn update_feed_cache_headers(&self, feed_id: i64, cache: &CacheHeaders) -> Result<()> {
+        self.conn.execute(
+            "UPDATE feeds SET etag = ?1, last_modified = ?2 WHERE id = ?3",
+            params![cache.etag, cache.last_modified, feed_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_feed_title_if_missing(&self, feed_id: i64, title: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE feeds SET title = ?1 WHERE id = ?2 AND (title IS NULL OR title = '')",
+            params![title, feed_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn update_feed_title(&self, feed_id: i64, title: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE feeds SET title = ?1 WHERE id = ?2",
+            params![title, feed_id],
+        )?;
         Ok(())
     }
 
+    /// Store the feed's self-description unless one was already recorded
+    pub fn set_feed_description_if_missing(&self, feed_id: i64, description: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE feeds SET description = ?1 WHERE id = ?2 AND (description IS NULL OR description = '')",
+            params![description, feed_id],
+        )?;
+        Ok(())
+    }
+
+    /// Feeds neither fetched successfully nor health-checked within
+    /// `max_age_secs`, least recently seen first
+    pub fn get_stale_feeds(&self, max_age_secs: i64, limit: usize) -> Result<Vec<Feed>> {
+        let cutoff = (Utc::now() - chrono::Duration::seconds(max_age_secs)).to_rfc3339();
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM feeds
+             WHERE MAX(COALESCE(last_fetched_at, ''), COALESCE(last_checked_at, '')) < ?1
+             ORDER BY MAX(COALESCE(last_fetched_at, ''), COALESCE(last_checked_at, '')) ASC
+             LIMIT ?2",
+            FEED_COLUMNS
+        ))?;
+        let feed_iter = stmt.query_map(params![cutoff, limit as i64], feed_from_row)?;
+
+        let mut feeds = Vec::new();
+        for feed in feed_iter {
+            feeds.push(feed?);
+        }
+        Ok(feeds)
+    }
+
+    pub fn get_unread_count_by_feed(&self, feed_id: i64) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM posts WHERE feed_id = ?1 AND is_read = 0",
+            params![feed_id],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    pub fn get_unhealthy_feeds_count(&self) -> Result<usize> {
+        self.get_count("SELECT COUNT(*) FROM feeds WHERE last_error IS NOT NULL")
+    }
+
     pub fn mark_as_archived(&self, post_id: i64) -> Result<()> {
         self.conn.execute(
             "UPDATE posts SET is_archived = NOT is_archived WHERE id = ?1",
@@ -285,39 +653,98 @@ impl Database {
 
     pub fn mark_as_read_later(&self, post_id: i64) -> Result<()> {
         self.conn.execute(
-            "UPDATE posts SET is_read_later = NOT is_read_later WHERE id = ?1",
-            params![post_id],
+            "UPDATE posts
+             SET is_read_later = NOT is_read_later,
+                 read_later_at = CASE WHEN is_read_later THEN NULL ELSE ?2 END,
+                 read_later_order = CASE WHEN is_read_later THEN NULL
+                     ELSE (SELECT COALESCE(MAX(read_later_order), 0) + 1 FROM posts) END
+             WHERE id = ?1",
+            params![post_id, Utc::now().to_rfc3339()],
         )?;
         Ok(())
     }
 
-    pub fn get_posts_by_category(&self, category: &str) -> Result<Vec<Post>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT p.id, p.feed_id, p.title, p.url, p.content, p.pub_date, p.is_read, p.is_bookmarked, p.is_archived, p.is_read_later, f.title
+    /// Case-insensitive substring match on post titles and content, newest first
+    pub fn search_posts(&self, term: &str, limit: Option<usize>, offset: usize) -> Result<Vec<Post>> {
+        let escaped = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        let pattern = format!("%{}%", escaped.to_lowercase());
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {}
+             FROM posts p
+             JOIN feeds f ON p.feed_id = f.id
+             WHERE unicode_lower(p.title) LIKE ?1 ESCAPE '\\'
+                OR unicode_lower(COALESCE(p.content, '')) LIKE ?1 ESCAPE '\\'
+             ORDER BY p.pub_date DESC LIMIT ?2 OFFSET ?3",
+            POST_COLUMNS
+        ))?;
+
+        let post_iter = stmt.query_map(params![pattern, sql_limit(limit), offset as i64], post_from_row)?;
+
+        let mut posts = Vec::new();
+        for post in post_iter {
+            posts.push(post?);
+        }
+        Ok(posts)
+    }
+
+    pub fn get_post_by_id(&self, post_id: i64) -> Result<Option<Post>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM posts p JOIN feeds f ON p.feed_id = f.id WHERE p.id = ?1",
+            POST_COLUMNS
+        ))?;
+        let mut rows = stmt.query_map(params![post_id], post_from_row)?;
+        rows.next().transpose()
+    }
+
+    /// Newest posts across every feed regardless of category or read state,
+    /// at most `per_feed` from any one feed (0 for no cap)
+    pub fn get_all_feeds_posts(&self, per_feed: usize, limit: Option<usize>, offset: usize) -> Result<Vec<Post>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {}
+             FROM (
+                SELECT *, ROW_NUMBER() OVER (PARTITION BY feed_id ORDER BY pub_date DESC) AS feed_rank
+                FROM posts
+             ) p
+             JOIN feeds f ON p.feed_id = f.id
+             WHERE ?1 < 0 OR p.feed_rank <= ?1
+             ORDER BY p.pub_date DESC LIMIT ?2 OFFSET ?3",
+            POST_COLUMNS
+        ))?;
+
+        let per_feed = sql_limit((per_feed > 0).then_some(per_feed));
+        let post_iter = stmt.query_map(params![per_feed, sql_limit(limit), offset as i64], post_from_row)?;
+
+        let mut posts = Vec::new();
+        for post in post_iter {
+            posts.push(post?);
+        }
+        Ok(posts)
+    }
+
+    pub fn count_all_feeds_posts(&self, per_feed: usize) -> Result<usize> {
+        let per_feed = sql_limit((per_feed > 0).then_some(per_feed));
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM (
+                SELECT ROW_NUMBER() OVER (PARTITION BY feed_id ORDER BY pub_date DESC) AS feed_rank
+                FROM posts
+             ) WHERE ?1 < 0 OR feed_rank <= ?1",
+            params![per_feed],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    pub fn get_posts_by_category(&self, category: &str, limit: Option<usize>, offset: usize) -> Result<Vec<Post>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {}
              FROM posts p
              JOIN feeds f ON p.feed_id = f.id
              WHERE f.category = ?1
-             ORDER BY p.pub_date DESC LIMIT 100"
-        )?;
-
-        let post_iter = stmt.query_map(params![category], |row| {
-            let pub_date_str: Option<String> = row.get(5)?;
-            let pub_date = pub_date_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok().map(|d| d.with_timezone(&Utc)));
-
-            Ok(Post {
-                id: row.get(0)?,
-                feed_id: row.get(1)?,
-                title: row.get(2)?,
-                url: row.get(3)?,
-                content: row.get(4)?,
-                pub_date,
-                is_read: row.get(6)?,
-                is_bookmarked: row.get(7)?,
-                is_archived: row.get(8)?,
-                is_read_later: row.get(9)?,
-                feed_title: row.get(10)?,
-            })
-        })?;
+             ORDER BY p.pub_date DESC LIMIT ?2 OFFSET ?3",
+            POST_COLUMNS
+        ))?;
+
+        let post_iter = stmt.query_map(params![category, sql_limit(limit), offset as i64], post_from_row)?;
 
         let mut posts = Vec::new();
         for post in post_iter {
@@ -327,15 +754,8 @@ impl Database {
     }
 
     pub fn get_feeds_by_category(&self, category: &str) -> Result<Vec<Feed>> {
-        let mut stmt = self.conn.prepare("SELECT id, url, title, category FROM feeds WHERE category = ?1")?;
-        let feed_iter = stmt.query_map(params![category], |row| {
-            Ok(Feed {
-                id: row.get(0)?,
-                url: row.get(1)?,
-                title: row.get(2)?,
-                category: row.get(3)?,
-            })
-        })?;
+        let mut stmt = self.conn.prepare(&format!("SELECT {} FROM feeds WHERE category = ?1", FEED_COLUMNS))?;
+        let feed_iter = stmt.query_map(params![category], feed_from_row)?;
 
         let mut feeds = Vec::new();
         for feed in feed_iter {
@@ -377,15 +797,109 @@ impl Database {
         Ok(())
     }
 
+    pub fn get_posts_by_feed(&self, feed_id: i64) -> Result<Vec<Post>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM posts p JOIN feeds f ON p.feed_id = f.id WHERE p.feed_id = ?1",
+            POST_COLUMNS
+        ))?;
+        let post_iter = stmt.query_map(params![feed_id], post_from_row)?;
+
+        let mut posts = Vec::new();
+        for post in post_iter {
+            posts.push(post?);
+        }
+        Ok(posts)
+    }
+
+    /// Copy read/star/archive/read-later state onto the post with `post.url`
+    pub fn set_post_state(&self, post: &Post) -> Result<()> {
+        self.conn.execute(
+            "UPDATE posts SET is_read = ?1, is_bookmarked = ?2, is_archived = ?3, is_read_later = ?4,
+                              read_later_at = ?5, read_later_order = ?6
+             WHERE url = ?7",
+            params![
+                post.is_read,
+                post.is_bookmarked,
+                post.is_archived,
+                post.is_read_later,
+                post.read_later_at.map(|d| d.to_rfc3339()),
+                post.read_later_order,
+                post.url,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Put back a deleted post under its original id and state
+    pub fn restore_post(&self, post: &Post) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO posts (id, feed_id, title, url, content, summary, pub_date, is_read, is_bookmarked,
+                                is_archived, is_read_later, read_later_at, read_later_order, enclosure_url)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![
+                post.id,
+                post.feed_id,
+                post.title,
+                post.url,
+                post.content,
+                post.summary,
+                post.pub_date.map(|d| d.to_rfc3339()),
+                post.is_read,
+                post.is_bookmarked,
+                post.is_archived,
+                post.is_read_later,
+                post.read_later_at.map(|d| d.to_rfc3339()),
+                post.read_later_order,
+                post.enclosure_url,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Put back a deleted feed and its posts. Cache validators aren't kept, so
+    /// the next refresh does a full fetch.
+    pub fn restore_feed(&self, feed: &Feed, posts: &[Post]) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO feeds (id, url, title, category, last_fetched_at, last_error, auto_category, description,
+                                consecutive_failures)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                feed.id,
+                feed.url,
+                feed.title,
+                feed.category,
+                feed.last_fetched_at.map(|d| d.to_rfc3339()),
+                feed.last_error,
+                feed.auto_category,
+                feed.description,
+                feed.consecutive_failures,
+            ],
+        )?;
+        for post in posts {
+            // A post re-fetched through another feed since the delete keeps that copy
+            let _ = self.restore_post(post);
+        }
+        Ok(())
+    }
+
+    /// Look a feed up by URL, normalized the same way as on insert
+    pub fn find_feed_by_url(&self, url: &str) -> Result<Option<Feed>> {
+        let url = normalize_feed_url(url);
+        let mut stmt = self.conn.prepare(&format!("SELECT {} FROM feeds WHERE url = ?1", FEED_COLUMNS))?;
+        let mut rows = stmt.query_map(params![url], feed_from_row)?;
+        rows.next().transpose()
+    }
+
     pub fn update_feed_category(&self, feed_id: i64, category: &str) -> Result<()> {
         self.conn.execute(
-            "UPDATE feeds SET category = ?1 WHERE id = ?2",
+            "UPDATE feeds SET category = ?1, auto_category = 0 WHERE id = ?2",
             params![category, feed_id],
         )?;
         Ok(())
     }
 
     pub fn add_feed_with_category(&self, url: &str, category: &str) -> Result<i64> {
+        let url = normalize_feed_url(url);
         self.conn.execute(
             "INSERT OR IGNORE INTO feeds (url, category) VALUES (?1, ?2)",
             params![url, category],
@@ -403,6 +917,19 @@ impl Database {
         Ok(count as usize)
     }
 
+    /// Unread posts across a category's feeds, as shown next to it in the
+    /// sidebar. The name is bound, so any quotes in it need no escaping.
+    pub fn get_category_post_count(&self, category: &str) -> usize {
+        self.conn
+            .query_row(
+                "SELECT COUNT(*) FROM posts p JOIN feeds f ON p.feed_id = f.id
+                 WHERE f.category = ?1 AND p.is_read = 0",
+                params![category],
+                |row| row.get::<_, i64>(0),
+            )
+            .map_or(0, |count| count as usize)
+    }
+
     pub fn get_category_stats(&self) -> Result<Vec<(String, usize)>> {
         let mut stmt = self.conn.prepare(
             "SELECT f.category, COUNT(p.id)
@@ -443,6 +970,15 @@ impl Database {
         Ok(())
     }
 
+    /// Move every feed in `from` into `to`, e.g. to sort out the Uncategorized bucket
+    pub fn move_category_feeds(&self, from: &str, to: &str) -> Result<usize> {
+        let moved = self.conn.execute(
+            "UPDATE feeds SET category = ?1, auto_category = 0 WHERE category = ?2",
+            params![to, from],
+        )?;
+        Ok(moved)
+    }
+
     pub fn rename_category(&self, old_name: &str, new_name: &str) -> Result<()> {
         self.conn.execute(
             "UPDATE feeds SET category = ?1 WHERE category = ?2",
@@ -506,6 +1042,140 @@ impl Database {
     pub fn get_total_feeds_count(&self) -> Result<usize> {
         self.get_count("SELECT COUNT(*) FROM feeds")
     }
+
+    /// Every dashboard total in one query, using conditional sums over the
+    /// posts table instead of a COUNT per flag
+    pub fn get_overview_counts(&self, stale_read_later_days: i64) -> Result<OverviewCounts> {
+        let cutoff = (Utc::now() - chrono::Duration::days(stale_read_later_days)).to_rfc3339();
+        self.conn.query_row(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(is_read = 1), 0),
+                    COALESCE(SUM(is_bookmarked = 1), 0),
+                    COALESCE(SUM(is_archived = 1), 0),
+                    COALESCE(SUM(is_read_later = 1), 0),
+                    COALESCE(SUM(is_read_later = 1 AND read_later_at < ?1), 0),
+                    (SELECT COUNT(*) FROM feeds),
+                    (SELECT COUNT(*) FROM feeds WHERE category = ?2)
+             FROM posts",
+            params![cutoff, UNCATEGORIZED],
+            |row| {
+                let count = |i| row.get::<_, i64>(i).map(|n| n as usize);
+                Ok(OverviewCounts {
+                    total_posts: count(0)?,
+                    read_posts: count(1)?,
+                    bookmarked_posts: count(2)?,
+                    archived_posts: count(3)?,
+                    read_later_posts: count(4)?,
+                    stale_read_later_posts: count(5)?,
+                    feeds: count(6)?,
+                    uncategorized_feeds: count(7)?,
+                })
+            },
+        )
+    }
+
+    /// A value saved with `set_preference`, if any
+    pub fn get_preference(&self, key: &str) -> Result<Option<String>> {
+        let mut stmt = self.conn.prepare("SELECT value FROM user_preferences WHERE key = ?1")?;
+        let mut rows = stmt.query_map(params![key], |row| row.get(0))?;
+        rows.next().transpose()
+    }
+
+    /// A preference parsed as `T` (a bool, a number, ...). A value that doesn't
+    /// parse reads as unset, so a changed type falls back to the default.
+    pub fn get_preference_as<T: std::str::FromStr>(&self, key: &str) -> Result<Option<T>> {
+        Ok(self.get_preference(key)?.and_then(|value| value.parse().ok()))
+    }
+
+    /// Save a preference, replacing any earlier value. Typed values are stored
+    /// with `to_string()` and read back with `get_preference_as`.
+    pub fn set_preference(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO user_preferences (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Read Later saves older than `days`, used to nudge the backlog
+    pub fn get_stale_read_later_count(&self, days: i64) -> Result<usize> {
+        let cutoff = (Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM posts WHERE is_read_later = 1 AND read_later_at < ?1",
+            params![cutoff],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    pub fn get_uncategorized_feeds_count(&self) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM feeds WHERE category = ?1",
+            params![UNCATEGORIZED],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+}
+
+/// Set when `lock_db` recovers a poisoned lock, until the UI picks it up
+static LOCK_RECOVERED: AtomicBool = AtomicBool::new(false);
+
+/// Lock the shared database, recovering the guard if another thread panicked while
+/// holding it so a single failed fetch doesn't take the whole app down with it.
+/// Printing here would draw over the TUI, so the recovery is flagged for
+/// `take_lock_recovered` instead.
+pub fn lock_db(db: &Mutex<Database>) -> MutexGuard<'_, Database> {
+    db.lock().unwrap_or_else(|poisoned| {
+        LOCK_RECOVERED.store(true, Ordering::Relaxed);
+        db.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
+/// Whether the database lock was recovered since the last call
+pub fn take_lock_recovered() -> bool {
+    LOCK_RECOVERED.swap(false, Ordering::Relaxed)
+}
+
+/// Normalize a feed URL so trivially different spellings map to one subscription:
+/// lowercases scheme and host, drops default ports and strips a trailing slash.
+/// URLs with `$VAR` in them are templates expanded at fetch time and are kept
+/// as written, since parsing would lowercase or percent-encode the variables.
+pub fn normalize_feed_url(url: &str) -> String {
+    let trimmed = url.trim();
+    if trimmed.contains('$') {
+        return trimmed.to_string();
+    }
+    let Ok(mut parsed) = reqwest::Url::parse(trimmed) else {
+        return trimmed.trim_end_matches('/').to_string();
+    };
+
+    let path = parsed.path().trim_end_matches('/').to_string();
+    parsed.set_path(&path);
+
+    let mut normalized = parsed.to_string();
+    if parsed.query().is_none() && parsed.fragment().is_none() {
+        while normalized.ends_with('/') && !normalized.ends_with("://") {
+            normalized.pop();
+        }
+    }
+    normalized
+}
+
+/// Post and feed totals behind the stats dashboard
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OverviewCounts {
+    pub total_posts: usize,
+    pub read_posts: usize,
+    pub bookmarked_posts: usize,
+    pub archived_posts: usize,
+    pub read_later_posts: usize,
+    /// Read Later saves older than the staleness cutoff
+    pub stale_read_later_posts: usize,
+    pub feeds: usize,
+    pub uncategorized_feeds: usize,
 }
 
 pub struct PostFilter {
@@ -513,6 +1183,39 @@ pub struct PostFilter {
     pub only_bookmarked: bool,
     pub only_archived: bool,
     pub only_read_later: bool,
+    /// Only posts from this category's feeds
+    pub category: Option<String>,
+    /// Maximum posts returned, `None` for no cap
+    pub limit: Option<usize>,
+    /// Posts to skip, for loading later pages
+    pub offset: usize,
+}
+
+/// Lowercased words of a title, ignoring punctuation and spacing, so
+/// "Rust 2.0 — Released!" and "rust 2.0: released" compare equal
+fn normalize_title(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// SQLite's built-in LOWER only folds ASCII, so search registers its own
+/// `unicode_lower` to match "Ärger" against "ärger"
+fn register_functions(conn: &Connection) -> Result<()> {
+    conn.create_scalar_function(
+        "unicode_lower",
+        1,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| Ok(ctx.get::<Option<String>>(0)?.map(|s| s.to_lowercase())),
+    )
+}
+
+/// SQLite treats a negative LIMIT as "no limit"
+fn sql_limit(limit: Option<usize>) -> i64 {
+    limit.map(|n| n as i64).unwrap_or(-1)
 }
 
 impl Database {
@@ -523,34 +1226,17 @@ impl Database {
 
         for category in categories {
             let query = format!(
-                "SELECT p.id, p.feed_id, p.title, p.url, p.content, p.pub_date, p.is_read, p.is_bookmarked, 
-                        COALESCE(p.is_archived, 0), COALESCE(p.is_read_later, 0), f.title
+                "SELECT {}
                  FROM posts p
                  JOIN feeds f ON p.feed_id = f.id
                  WHERE f.category = ?1 AND p.is_read = 0
                  ORDER BY p.pub_date DESC
-                 LIMIT ?2"
+                 LIMIT ?2",
+                POST_COLUMNS
             );
 
             let mut stmt = self.conn.prepare(&query)?;
-            let post_iter = stmt.query_map(params![category, per_category_limit as i64], |row| {
-                let pub_date_str: Option<String> = row.get(5)?;
-                let pub_date = pub_date_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok().map(|d| d.with_timezone(&Utc)));
-
-                Ok(Post {
-                    id: row.get(0)?,
-                    feed_id: row.get(1)?,
-                    title: row.get(2)?,
-                    url: row.get(3)?,
-                    content: row.get(4)?,
-                    pub_date,
-                    is_read: row.get(6)?,
-                    is_bookmarked: row.get(7)?,
-                    is_archived: row.get(8)?,
-                    is_read_later: row.get(9)?,
-                    feed_title: row.get(10)?,
-                })
-            })?;
+            let post_iter = stmt.query_map(params![category, per_category_limit as i64], post_from_row)?;
 
             for post in post_iter {
                 all_posts.push(post?);
@@ -558,12 +1244,11 @@ impl Database {
         }
 
         // Sort all posts by pub_date descending
-        all_posts.sort_by(|a, b| b.pub_date.cmp(&a.pub_date));
+        all_posts.sort_by_key(|p| std::cmp::Reverse(p.pub_date));
         Ok(all_posts)
     }
 
     /// Update post content (for fetching full article)
-    #[allow(dead_code)]
     pub fn update_post_content(&self, post_id: i64, content: &str) -> Result<()> {
         self.conn.execute(
             "UPDATE posts SET content = ?1 WHERE id = ?2",
@@ -572,3 +1257,164 @@ impl Database {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory_db() -> Database {
+        Database::init_with_path(":memory:").unwrap()
+    }
+
+    /// Insert a feed row as an older version would have, without normalizing
+    fn insert_raw_feed(db: &Database, url: &str, title: Option<&str>) -> i64 {
+        db.conn
+            .execute("INSERT INTO feeds (url, title) VALUES (?1, ?2)", params![url, title])
+            .unwrap();
+        db.conn.last_insert_rowid()
+    }
+
+    #[test]
+    fn normalize_lowercases_scheme_and_host_only() {
+        assert_eq!(normalize_feed_url("HTTPS://Example.COM/Feed.xml"), "https://example.com/Feed.xml");
+    }
+
+    #[test]
+    fn normalize_strips_trailing_slash_and_default_port() {
+        assert_eq!(normalize_feed_url("https://example.com:443/feed/"), "https://example.com/feed");
+        assert_eq!(normalize_feed_url("http://example.com:80/"), "http://example.com");
+        assert_eq!(normalize_feed_url("http://example.com:8080/rss/"), "http://example.com:8080/rss");
+    }
+
+    #[test]
+    fn normalize_keeps_query_and_trims_whitespace() {
+        assert_eq!(normalize_feed_url("  https://example.com/feed/?page=2 "), "https://example.com/feed?page=2");
+    }
+
+    #[test]
+    fn normalize_leaves_unparseable_input_mostly_alone() {
+        assert_eq!(normalize_feed_url("not a url/"), "not a url");
+    }
+
+    #[test]
+    fn normalize_keeps_url_templates_as_written() {
+        assert_eq!(normalize_feed_url(" https://$FEED_HOST/feed/ "), "https://$FEED_HOST/feed/");
+        assert_eq!(normalize_feed_url("https://Example.com/${FEED_PATH}"), "https://Example.com/${FEED_PATH}");
+    }
+
+    #[test]
+    fn migration_normalizes_stored_urls() {
+        let db = memory_db();
+        let id = insert_raw_feed(&db, "HTTPS://Example.com/feed/", None);
+
+        db.normalize_stored_feed_urls().unwrap();
+
+        let feed = db.find_feed_by_url("https://example.com/feed").unwrap().unwrap();
+        assert_eq!(feed.id, id);
+    }
+
+    #[test]
+    fn migration_merges_feeds_that_normalize_to_one_url() {
+        let db = memory_db();
+        let keep = db.add_feed("https://example.com/feed").unwrap();
+        let duplicate = insert_raw_feed(&db, "https://EXAMPLE.com/feed/", Some("Example"));
+        db.insert_post(duplicate, "Hello", "https://example.com/hello", None, None, None).unwrap();
+
+        db.normalize_stored_feed_urls().unwrap();
+
+        let feeds = db.get_feeds().unwrap();
+        assert_eq!(feeds.len(), 1);
+        assert_eq!(feeds[0].id, keep);
+        assert_eq!(feeds[0].title.as_deref(), Some("Example"));
+        let post_feed: i64 = db
+            .conn
+            .query_row("SELECT feed_id FROM posts WHERE url = 'https://example.com/hello'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(post_feed, keep);
+    }
+
+    #[test]
+    fn preferences_insert_then_replace() {
+        let db = memory_db();
+        assert_eq!(db.get_preference("sort_mode").unwrap(), None);
+
+        db.set_preference("sort_mode", "newest").unwrap();
+        assert_eq!(db.get_preference("sort_mode").unwrap().as_deref(), Some("newest"));
+
+        db.set_preference("sort_mode", "oldest").unwrap();
+        assert_eq!(db.get_preference("sort_mode").unwrap().as_deref(), Some("oldest"));
+        let rows: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM user_preferences WHERE key = 'sort_mode'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(rows, 1);
+    }
+
+    #[test]
+    fn typed_preferences_round_trip() {
+        let db = memory_db();
+        db.set_preference("show_read", &true.to_string()).unwrap();
+        db.set_preference("last_tab", &3.to_string()).unwrap();
+        assert_eq!(db.get_preference_as::<bool>("show_read").unwrap(), Some(true));
+        assert_eq!(db.get_preference_as::<usize>("last_tab").unwrap(), Some(3));
+
+        // A value of the wrong type reads as unset
+        db.set_preference("last_tab", "second").unwrap();
+        assert_eq!(db.get_preference_as::<usize>("last_tab").unwrap(), None);
+    }
+
+    #[test]
+    fn lock_db_recovers_a_poisoned_lock_and_flags_it() {
+        let db = std::sync::Arc::new(Mutex::new(memory_db()));
+        let poisoner = db.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("fetch task crashed");
+        })
+        .join();
+        assert!(db.is_poisoned());
+
+        lock_db(&db).set_preference("still", "works").unwrap();
+
+        assert!(take_lock_recovered());
+        assert!(!take_lock_recovered());
+        assert!(!db.is_poisoned());
+    }
+
+    #[test]
+    fn health_check_records_health_without_stamping_the_fetch() {
+        let db = memory_db();
+        let checked = db.add_feed("https://example.com/checked").unwrap();
+        let unchecked = db.add_feed("https://example.com/unchecked").unwrap();
+
+        db.record_feed_check(checked, Err("HTTP 404")).unwrap();
+
+        let feed = db.find_feed_by_url("https://example.com/checked").unwrap().unwrap();
+        assert_eq!(feed.last_error.as_deref(), Some("HTTP 404"));
+        assert_eq!(feed.consecutive_failures, 1);
+        assert_eq!(feed.last_fetched_at, None);
+        // Checked just now, so only the other feed is due
+        let stale: Vec<_> = db.get_stale_feeds(60, 10).unwrap().into_iter().map(|f| f.id).collect();
+        assert_eq!(stale, vec![unchecked]);
+
+        db.record_feed_check(checked, Ok(())).unwrap();
+        let feed = db.find_feed_by_url("https://example.com/checked").unwrap().unwrap();
+        assert_eq!(feed.last_error, None);
+        assert_eq!(feed.consecutive_failures, 0);
+        assert_eq!(feed.last_fetched_at, None);
+    }
+
+    #[test]
+    fn search_folds_non_ascii_case() {
+        let db = memory_db();
+        let feed = db.add_feed("https://example.com/feed").unwrap();
+        db.insert_post(feed, "ÄRGER IM ÜBERBLICK", "https://example.com/1", None, None, None).unwrap();
+        db.insert_post(feed, "Unrelated", "https://example.com/2", Some("Straße in Ελλάδα"), None, None).unwrap();
+
+        let titles = |term: &str| -> Vec<String> {
+            db.search_posts(term, None, 0).unwrap().into_iter().map(|p| p.title).collect()
+        };
+        assert_eq!(titles("ärger"), vec!["ÄRGER IM ÜBERBLICK"]);
+        assert_eq!(titles("ΕΛΛΆΔΑ"), vec!["Unrelated"]);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::db::Database;
+use std::path::Path;
+
+/// Number of recent feed errors included in a diagnostics report
+const MAX_REPORTED_ERRORS: usize = 5;
+
+/// Build a plain-text block describing the environment, suitable for pasting into a bug report
+pub fn collect(config_path: &Path, db_path: &Path, db: Option<&Database>) -> String {
+    let mut lines = vec![
+        format!("news-feed {}", env!("CARGO_PKG_VERSION")),
+        format!("os: {} ({})", std::env::consts::OS, std::env::consts::ARCH),
+        format!("terminal: {}", env_or_unset("TERM")),
+        format!("term program: {}", env_or_unset("TERM_PROGRAM")),
+        format!("colorterm: {}", env_or_unset("COLORTERM")),
+        format!("config: {}{}", config_path.display(), exists_note(config_path)),
+        format!("database: {}{}", db_path.display(), exists_note(db_path)),
+    ];
+
+    if let Some(db) = db {
+        lines.push(format!("feeds: {}", db.get_total_feeds_count().unwrap_or(0)));
+        lines.push(format!("posts: {}", db.get_total_posts_count().unwrap_or(0)));
+
+        let failing: Vec<_> = db
+            .get_feeds()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|f| f.last_error.map(|e| (f.url, e)))
+            .take(MAX_REPORTED_ERRORS)
+            .collect();
+
+        if !failing.is_empty() {
+            lines.push("recent fetch errors:".to_string());
+            for (url, error) in failing {
+                lines.push(format!("  {}: {}", url, error));
+            }
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn env_or_unset(key: &str) -> String {
+    std::env::var(key).unwrap_or_else(|_| "(unset)".to_string())
+}
+
+fn exists_note(path: &Path) -> &'static str {
+    if path.exists() { "" } else { " (missing)" }
+}
@@ -0,0 +1,246 @@
+use crate::db::{Database, Post};
+use crate::db::{Feed, PostFilter};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Json,
+    Markdown,
+    Digest(DigestFormat),
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown | ExportFormat::Digest(DigestFormat::Markdown) => "md",
+            ExportFormat::Digest(DigestFormat::Text) => "txt",
+        }
+    }
+}
+
+/// Layout used for the shareable digest
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestFormat {
+    #[default]
+    Markdown,
+    Text,
+}
+
+/// Longest one-line summary included per post in a digest
+const DIGEST_SUMMARY_LEN: usize = 140;
+
+pub fn posts_to_json(posts: &[Post]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(posts)
+}
+
+pub fn posts_to_markdown(title: &str, posts: &[Post]) -> String {
+    let mut md = format!("# {}\n\n", title);
+    for post in posts {
+        let feed = post.feed_title.as_deref().unwrap_or("Unknown");
+        let date = post
+            .pub_date
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+
+        if post.url.is_empty() {
+            md.push_str(&format!("- {} — {} {}\n", post.title, feed, date));
+        } else {
+            md.push_str(&format!("- [{}]({}) — {} {}\n", post.title, post.url, feed, date));
+        }
+    }
+    md
+}
+
+/// Compile posts into a shareable roundup: title, link and a one-line summary each
+pub fn posts_to_digest(title: &str, posts: &[Post], format: DigestFormat) -> String {
+    let mut out = match format {
+        DigestFormat::Markdown => format!("# {}\n\n", title),
+        DigestFormat::Text => format!("{}\n{}\n\n", title, "=".repeat(title.chars().count())),
+    };
+
+    for post in posts {
+        let summary = summarize(post.content.as_deref().unwrap_or(""));
+        match format {
+            DigestFormat::Markdown => {
+                if post.url.is_empty() {
+                    out.push_str(&format!("- **{}**", post.title));
+                } else {
+                    out.push_str(&format!("- **[{}]({})**", post.title, post.url));
+                }
+                if !summary.is_empty() {
+                    out.push_str(&format!("  \n  {}", summary));
+                }
+                out.push('\n');
+            }
+            DigestFormat::Text => {
+                out.push_str(&format!("* {}\n", post.title));
+                if !post.url.is_empty() {
+                    out.push_str(&format!("  {}\n", post.url));
+                }
+                if !summary.is_empty() {
+                    out.push_str(&format!("  {}\n", summary));
+                }
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// First non-empty line of the post body as plain text, shortened for a digest
+fn summarize(html: &str) -> String {
+    let text = html2text::from_read(html.as_bytes(), 1000).unwrap_or_default();
+    let line = text.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("");
+    if line.chars().count() > DIGEST_SUMMARY_LEN {
+        let cut: String = line.chars().take(DIGEST_SUMMARY_LEN - 1).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Turn a view title into something safe to use in a file name
+pub fn slugify(name: &str) -> String {
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    slug.split('-').filter(|s| !s.is_empty()).collect::<Vec<_>>().join("-")
+}
+
+/// Write a snapshot of `posts` to `<dir>/exports/<view>-<timestamp>.<ext>`
+pub fn export_posts(
+    dir: &Path,
+    view_title: &str,
+    posts: &[Post],
+    format: ExportFormat,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let export_dir = dir.join("exports");
+    std::fs::create_dir_all(&export_dir)?;
+
+    let file_name = format!(
+        "{}-{}.{}",
+        slugify(view_title),
+        Local::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+    );
+    let path = export_dir.join(file_name);
+
+    let content = match format {
+        ExportFormat::Json => posts_to_json(posts)?,
+        ExportFormat::Markdown => posts_to_markdown(view_title, posts),
+        ExportFormat::Digest(digest_format) => posts_to_digest(view_title, posts, digest_format),
+    };
+    std::fs::write(&path, content)?;
+    Ok(path)
+}
+
+/// Write one Markdown reading list per category to `<dir>/<category>.md`,
+/// returning each file written with its post count
+pub fn export_reading_lists(
+    db: &Database,
+    dir: &Path,
+    unread_only: bool,
+) -> Result<Vec<(PathBuf, usize)>, Box<dyn Error>> {
+    std::fs::create_dir_all(dir)?;
+
+    let mut written = Vec::new();
+    for category in db.get_categories()? {
+        let mut posts = db.get_posts_by_category(&category, None, 0)?;
+        if unread_only {
+            posts.retain(|p| !p.is_read);
+        }
+        if posts.is_empty() {
+            continue;
+        }
+
+        let slug = slugify(&category);
+        let file_name = if slug.is_empty() { "category".to_string() } else { slug };
+        let path = dir.join(format!("{}.md", file_name));
+        std::fs::write(&path, posts_to_markdown(&category, &posts))?;
+        written.push((path, posts.len()));
+    }
+    Ok(written)
+}
+
+/// Bumped if the backup layout changes incompatibly
+const BACKUP_VERSION: u32 = 1;
+
+/// Every feed and post with its reading state, for `export-json` / `import-json`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Backup {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub feeds: Vec<Feed>,
+    pub posts: Vec<Post>,
+}
+
+pub fn backup_to_json(db: &Database) -> Result<String, Box<dyn Error>> {
+    let posts = db.get_posts(PostFilter {
+        only_unread: false,
+        only_bookmarked: false,
+        only_archived: false,
+        only_read_later: false,
+        category: None,
+        limit: None,
+        offset: 0,
+    })?;
+    let backup = Backup {
+        version: BACKUP_VERSION,
+        exported_at: Utc::now(),
+        feeds: db.get_feeds()?,
+        posts,
+    };
+    Ok(serde_json::to_string_pretty(&backup)?)
+}
+
+/// Restore a backup. Feeds and posts are matched by URL, so importing the
+/// same file twice changes nothing; for posts already present, the backup's
+/// read/star/archive/read-later state wins. Returns `(feeds, posts)` restored.
+pub fn restore_backup(db: &Database, json: &str) -> Result<(usize, usize), Box<dyn Error>> {
+    let backup: Backup = serde_json::from_str(json)?;
+    if backup.version > BACKUP_VERSION {
+        return Err(format!("backup version {} is newer than this build supports", backup.version).into());
+    }
+
+    // Backup feed ids -> ids in this database
+    let mut feed_ids = std::collections::HashMap::new();
+    for feed in &backup.feeds {
+        let id = db.add_feed_with_category(&feed.url, &feed.category)?;
+        if let Some(title) = &feed.title {
+            db.set_feed_title_if_missing(id, title)?;
+        }
+        if let Some(description) = &feed.description {
+            db.set_feed_description_if_missing(id, description)?;
+        }
+        feed_ids.insert(feed.id, id);
+    }
+
+    let mut restored = 0;
+    for post in &backup.posts {
+        let Some(&feed_id) = feed_ids.get(&post.feed_id) else {
+            continue;
+        };
+        db.insert_post(
+            feed_id,
+            &post.title,
+            &post.url,
+            post.content.as_deref(),
+            post.summary.as_deref(),
+            post.pub_date,
+        )?;
+        if let Some(enclosure_url) = &post.enclosure_url {
+            db.set_post_enclosure_if_missing(&post.url, enclosure_url)?;
+        }
+        db.set_post_state(post)?;
+        restored += 1;
+    }
+
+    Ok((feed_ids.len(), restored))
+}
@@ -0,0 +1,61 @@
+use reqwest::Client;
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashMap;
+use std::error::Error;
+
+/// Paragraph text (in bytes) the best candidate needs before it's trusted as
+/// the article body; below this the page is likely a paywall or an index
+const MIN_ARTICLE_TEXT: usize = 250;
+
+/// Fetch a post's web page and extract its main content as HTML
+pub async fn fetch_article(client: &Client, url: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let resp = client.get(url).send().await?.error_for_status()?;
+    let is_html = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_none_or(|content_type| content_type.contains("html"));
+    if !is_html {
+        return Err("not an HTML page".into());
+    }
+
+    let html = resp.text().await?;
+    extract_article(&html).ok_or_else(|| "no article text found (the page may be paywalled)".into())
+}
+
+/// Readability-style extraction: every `<p>` credits its text length to its
+/// parent, and half of it to its grandparent. The highest-scoring element is
+/// taken as the article. Paragraphs inside navigation, headers, footers,
+/// sidebars and forms don't count.
+pub fn extract_article(html: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let paragraphs = Selector::parse("p").ok()?;
+
+    let mut scores = HashMap::new();
+    for paragraph in document.select(&paragraphs) {
+        if in_boilerplate(paragraph) {
+            continue;
+        }
+        let length: usize = paragraph.text().map(|t| t.trim().len()).sum();
+        let mut ancestors = paragraph.ancestors().filter_map(ElementRef::wrap);
+        if let Some(parent) = ancestors.next() {
+            *scores.entry(parent.id()).or_insert(0) += length;
+        }
+        if let Some(grandparent) = ancestors.next() {
+            *scores.entry(grandparent.id()).or_insert(0) += length / 2;
+        }
+    }
+
+    let (best, score) = scores.into_iter().max_by_key(|(_, score)| *score)?;
+    if score < MIN_ARTICLE_TEXT {
+        return None;
+    }
+    document.tree.get(best).and_then(ElementRef::wrap).map(|element| element.inner_html())
+}
+
+fn in_boilerplate(element: ElementRef) -> bool {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|a| matches!(a.value().name(), "nav" | "header" | "footer" | "aside" | "form"))
+}
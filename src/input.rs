@@ -1,6 +1,7 @@
 #[allow(dead_code)]
 pub struct TextInput {
     pub value: String,
+    /// Cursor position in chars, not bytes; see `byte_offset`
     pub cursor_position: usize,
 }
 
@@ -14,13 +15,21 @@ impl TextInput {
     }
 
     pub fn insert_char(&mut self, c: char) {
-        self.value.insert(self.cursor_position, c);
+        let at = self.byte_offset(self.cursor_position);
+        self.value.insert(at, c);
         self.cursor_position += 1;
     }
 
+    pub fn insert_str(&mut self, text: &str) {
+        let at = self.byte_offset(self.cursor_position);
+        self.value.insert_str(at, text);
+        self.cursor_position += text.chars().count();
+    }
+
     pub fn delete_char(&mut self) {
         if self.cursor_position > 0 {
-            self.value.remove(self.cursor_position - 1);
+            let at = self.byte_offset(self.cursor_position - 1);
+            self.value.remove(at);
             self.cursor_position -= 1;
         }
     }
@@ -32,15 +41,80 @@ impl TextInput {
     }
 
     pub fn move_cursor_right(&mut self) {
-        if self.cursor_position < self.value.len() {
+        if self.cursor_position < self.char_count() {
             self.cursor_position += 1;
         }
     }
 
+    /// Move to the start of the word before the cursor. Words are runs of
+    /// letters and digits, so in a URL each path segment is a word.
+    pub fn move_word_left(&mut self) {
+        self.cursor_position = self.word_start_before(self.cursor_position);
+    }
+
+    /// Move past the end of the word after the cursor
+    pub fn move_word_right(&mut self) {
+        let after: Vec<char> = self.value.chars().skip(self.cursor_position).collect();
+        let mut end = 0;
+        while end < after.len() && !after[end].is_alphanumeric() {
+            end += 1;
+        }
+        while end < after.len() && after[end].is_alphanumeric() {
+            end += 1;
+        }
+        self.cursor_position += end;
+    }
+
+    /// Delete from the start of the word before the cursor up to the cursor (Ctrl+W)
+    pub fn delete_word(&mut self) {
+        let start = self.word_start_before(self.cursor_position);
+        let range = self.byte_offset(start)..self.byte_offset(self.cursor_position);
+        self.value.replace_range(range, "");
+        self.cursor_position = start;
+    }
+
+    /// Delete everything before the cursor (Ctrl+U)
+    pub fn delete_to_start(&mut self) {
+        let end = self.byte_offset(self.cursor_position);
+        self.value.replace_range(..end, "");
+        self.cursor_position = 0;
+    }
+
+    /// The text before and after the cursor
+    pub fn split_at_cursor(&self) -> (&str, &str) {
+        self.value.split_at(self.byte_offset(self.cursor_position))
+    }
+
     pub fn clear(&mut self) {
         self.value.clear();
         self.cursor_position = 0;
     }
+
+    fn char_count(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    /// Byte offset of the char at `char_index`, or the end of the value
+    fn byte_offset(&self, char_index: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(char_index)
+            .map_or(self.value.len(), |(i, _)| i)
+    }
+
+    /// Char index where the word ending at or before `position` starts,
+    /// skipping any separators directly before it
+    fn word_start_before(&self, position: usize) -> usize {
+        let before: Vec<char> = self.value.chars().take(position).collect();
+        let mut start = before.len();
+        while start > 0 && !before[start - 1].is_alphanumeric() {
+            start -= 1;
+        }
+        while start > 0 && before[start - 1].is_alphanumeric() {
+            start -= 1;
+        }
+        start
+    }
 }
 
 #[allow(dead_code)]
@@ -49,3 +123,71 @@ impl Default for TextInput {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(text: &str) -> TextInput {
+        let mut input = TextInput::new();
+        for c in text.chars() {
+            input.insert_char(c);
+        }
+        input
+    }
+
+    #[test]
+    fn types_and_deletes_accented_text() {
+        let mut input = typed("café");
+        assert_eq!(input.cursor_position, 4);
+        input.delete_char();
+        assert_eq!(input.value, "caf");
+        input.insert_char('é');
+        input.insert_char('s');
+        assert_eq!(input.value, "cafés");
+    }
+
+    #[test]
+    fn edits_in_the_middle_of_emoji() {
+        let mut input = typed("a🦀b🎉c");
+        input.move_cursor_left();
+        input.move_cursor_left();
+        assert_eq!(input.split_at_cursor(), ("a🦀b", "🎉c"));
+
+        input.delete_char();
+        assert_eq!(input.value, "a🦀🎉c");
+        input.delete_char();
+        assert_eq!(input.value, "a🎉c");
+        input.insert_char('ñ');
+        assert_eq!(input.value, "añ🎉c");
+        assert_eq!(input.cursor_position, 2);
+    }
+
+    #[test]
+    fn inserts_at_start_and_end() {
+        let mut input = typed("naïve");
+        for _ in 0..10 {
+            input.move_cursor_left();
+        }
+        assert_eq!(input.cursor_position, 0);
+        input.insert_str("😀 ");
+        for _ in 0..10 {
+            input.move_cursor_right();
+        }
+        input.insert_char('✓');
+        assert_eq!(input.value, "😀 naïve✓");
+        assert_eq!(input.cursor_position, 8);
+    }
+
+    #[test]
+    fn word_editing_keeps_char_boundaries() {
+        let mut input = typed("über/straße");
+        input.delete_word();
+        assert_eq!(input.value, "über/");
+        input.move_word_left();
+        assert_eq!(input.cursor_position, 0);
+        input.move_word_right();
+        input.delete_to_start();
+        assert_eq!(input.value, "/");
+    }
+}
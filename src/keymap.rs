@@ -0,0 +1,274 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{BTreeMap, HashMap};
+
+/// Where an action applies. Pane bindings are looked up before global ones,
+/// so a pane can reuse a global key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Context {
+    Global,
+    Sidebar,
+    Posts,
+    Article,
+}
+
+macro_rules! actions {
+    ($($variant:ident => $name:literal, [$(($context:ident, [$($key:literal),*])),*];)*) => {
+        /// Everything the main panes can do from the keyboard, by config name
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Action {
+            $($variant,)*
+        }
+
+        impl Action {
+            pub const ALL: &[Action] = &[$(Action::$variant,)*];
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Action::$variant => $name,)*
+                }
+            }
+
+            /// Contexts the action applies in, with its default keys in each
+            pub fn defaults(self) -> &'static [(Context, &'static [&'static str])] {
+                match self {
+                    $(Action::$variant => &[$((Context::$context, &[$($key),*])),*],)*
+                }
+            }
+        }
+    };
+}
+
+actions! {
+    Quit => "quit", [(Global, ["q", "Q"])];
+    Help => "help", [(Global, ["?"])];
+    Dashboard => "dashboard", [(Global, ["g", "0"])];
+    CopyDiagnostics => "copy_diagnostics", [(Global, ["D"])];
+    OpenDataDirs => "open_data_dirs", [(Global, ["O"])];
+    ReloadConfig => "reload_config", [(Global, ["R"])];
+    CycleTheme => "cycle_theme", [(Global, ["t"])];
+    UndoDelete => "undo_delete", [(Global, ["U"])];
+    Search => "search", [(Global, ["/"])];
+    ToggleDebug => "toggle_debug", [(Global, ["F12"])];
+    FocusLeft => "focus_left", [(Global, ["h", "Left"])];
+    FocusRight => "focus_right", [(Global, ["l", "Right"])];
+    NextPane => "next_pane", [(Global, ["Tab"])];
+    PreviousPane => "previous_pane", [(Global, ["BackTab"])];
+    ViewFresh => "view_fresh", [(Global, ["1"])];
+    ViewAllFeeds => "view_all_feeds", [(Global, ["2"])];
+    ViewStarred => "view_starred", [(Global, ["3"])];
+    ViewReadLater => "view_read_later", [(Global, ["4"])];
+    ViewArchived => "view_archived", [(Global, ["5"])];
+    FeedManager => "feed_manager", [(Global, ["6"])];
+
+    NextItem => "next_item", [(Sidebar, ["j", "Down"])];
+    PreviousItem => "previous_item", [(Sidebar, ["k", "Up"])];
+    SelectItem => "select_item", [(Sidebar, ["Enter"])];
+    AddFeed => "add_feed", [(Sidebar, ["a", "+"]), (Posts, ["+"])];
+    AddCategory => "add_category", [(Sidebar, ["n"])];
+    EditFeeds => "edit_feeds", [(Sidebar, ["e"])];
+    MoveFeeds => "move_feeds", [(Sidebar, ["m"])];
+    DeleteCategory => "delete_category", [(Sidebar, ["d"])];
+
+    NextPost => "next_post", [(Posts, ["j", "Down"])];
+    PreviousPost => "previous_post", [(Posts, ["k", "Up"])];
+    OpenPost => "open_post", [(Posts, ["Enter"])];
+    ToggleRead => "toggle_read", [(Posts, ["m"])];
+    ToggleShowRead => "toggle_show_read", [(Posts, ["u"])];
+    CycleFilter => "cycle_filter", [(Posts, ["f"])];
+    CycleSort => "cycle_sort", [(Posts, ["s"])];
+    ClearSearch => "clear_search", [(Posts, ["Esc"])];
+    FilterList => "filter_list", [(Posts, ["F"])];
+    QueueUp => "queue_up", [(Posts, ["K"])];
+    QueueDown => "queue_down", [(Posts, ["J"])];
+    DeletePost => "delete_post", [(Posts, ["d"])];
+    DeleteCategoryPosts => "delete_category_posts", [(Posts, ["P"])];
+    ExportJson => "export_json", [(Posts, ["x"])];
+    ExportMarkdown => "export_markdown", [(Posts, ["X"])];
+    ShareDigest => "share_digest", [(Posts, ["S"])];
+    Refresh => "refresh", [(Posts, ["r"])];
+
+    Top => "top", [(Posts, ["g"]), (Article, ["g"])];
+    Bottom => "bottom", [(Posts, ["G"]), (Article, ["G"])];
+    HalfPageDown => "half_page_down", [(Posts, ["ctrl+d"]), (Article, ["ctrl+d"])];
+    HalfPageUp => "half_page_up", [(Posts, ["ctrl+u"]), (Article, ["ctrl+u"])];
+    ToggleBookmark => "toggle_bookmark", [(Posts, ["b"]), (Article, ["b"])];
+    ToggleReadLater => "toggle_read_later", [(Posts, ["l"]), (Article, ["l"])];
+    ToggleArchived => "toggle_archived", [(Posts, ["a"]), (Article, ["a"])];
+    OpenBrowser => "open_browser", [(Posts, ["o"]), (Article, ["o"])];
+    CopyUrl => "copy_url", [(Posts, ["y"]), (Article, ["y"])];
+
+    CloseArticle => "close_article", [(Article, ["Esc", "Backspace", "h"])];
+    ScrollDown => "scroll_down", [(Article, ["j", "Down"])];
+    ScrollUp => "scroll_up", [(Article, ["k", "Up"])];
+    PageDown => "page_down", [(Article, ["PageDown"])];
+    PageUp => "page_up", [(Article, ["PageUp"])];
+    ScrollLeft => "scroll_left", [(Article, ["Left"])];
+    ScrollRight => "scroll_right", [(Article, ["Right"])];
+    NextArticle => "next_article", [(Article, ["J"])];
+    PreviousArticle => "previous_article", [(Article, ["K"])];
+    ToggleSummary => "toggle_summary", [(Article, ["s"])];
+    FullText => "full_text", [(Article, ["f"])];
+    LivePage => "live_page", [(Article, ["L"])];
+    SaveLivePage => "save_live_page", [(Article, ["ctrl+s"])];
+    PlayEnclosure => "play_enclosure", [(Article, ["p"])];
+    ToggleWrap => "toggle_wrap", [(Article, ["w"])];
+    AutoScroll => "auto_scroll", [(Article, ["A"])];
+    PauseAutoScroll => "pause_auto_scroll", [(Article, ["Space"])];
+}
+
+impl Action {
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.iter().copied().find(|action| action.name() == name)
+    }
+}
+
+/// A key plus the modifiers that matter for matching. Shift is folded into
+/// the character itself, so "J" and "shift+j" are the same binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers = modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        let code = match code {
+            KeyCode::Char(c) if modifiers.is_empty() => KeyCode::Char(c),
+            // Terminals report ctrl+D and ctrl+d alike
+            KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+            KeyCode::BackTab => {
+                modifiers = KeyModifiers::NONE;
+                KeyCode::BackTab
+            }
+            other => other,
+        };
+        KeyBinding { code, modifiers }
+    }
+
+    pub fn from_event(event: KeyEvent) -> Self {
+        KeyBinding::new(event.code, event.modifiers)
+    }
+}
+
+/// Parse a key string such as `j`, `G`, `Enter`, `PageDown`, `F12`, `Space`
+/// or `ctrl+d` / `alt+b`
+pub fn parse_key(value: &str) -> Result<KeyBinding, String> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = value.trim();
+    loop {
+        let lower = rest.to_ascii_lowercase();
+        if let Some(stripped) = lower.strip_prefix("ctrl+") {
+            modifiers |= KeyModifiers::CONTROL;
+            rest = &rest[rest.len() - stripped.len()..];
+        } else if let Some(stripped) = lower.strip_prefix("alt+") {
+            modifiers |= KeyModifiers::ALT;
+            rest = &rest[rest.len() - stripped.len()..];
+        } else {
+            break;
+        }
+    }
+
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match rest.to_ascii_lowercase().as_str() {
+            "enter" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "delete" => KeyCode::Delete,
+            name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                _ => return Err(format!("unknown key '{}'", value)),
+            },
+        },
+    };
+    Ok(KeyBinding::new(code, modifiers))
+}
+
+/// Resolved key → action lookup for the main panes
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    bindings: HashMap<(Context, KeyBinding), Action>,
+}
+
+impl KeyMap {
+    /// Defaults with the `[keybindings]` overrides applied. An override
+    /// replaces all default keys of that action, and wins over any default
+    /// using the same key. Overrides are validated when the config loads.
+    pub fn new(overrides: &BTreeMap<String, Vec<String>>) -> Self {
+        let mut bindings = HashMap::new();
+        let mut overridden = Vec::new();
+
+        for &action in Action::ALL {
+            match overrides.get(action.name()) {
+                Some(keys) => overridden.push((action, keys)),
+                None => {
+                    for (context, keys) in action.defaults() {
+                        for key in keys.iter().filter_map(|k| parse_key(k).ok()) {
+                            bindings.insert((*context, key), action);
+                        }
+                    }
+                }
+            }
+        }
+        for (action, keys) in overridden {
+            for (context, _) in action.defaults() {
+                for key in keys.iter().filter_map(|k| parse_key(k).ok()) {
+                    bindings.insert((*context, key), action);
+                }
+            }
+        }
+        KeyMap { bindings }
+    }
+
+    /// The action for `event` in the focused pane, falling back to global keys
+    pub fn action(&self, context: Context, event: KeyEvent) -> Option<Action> {
+        let key = KeyBinding::from_event(event);
+        self.bindings
+            .get(&(context, key))
+            .or_else(|| self.bindings.get(&(Context::Global, key)))
+            .copied()
+    }
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        KeyMap::new(&BTreeMap::new())
+    }
+}
+
+/// Commented list of every action and its default keys, appended under the
+/// `[keybindings]` table of a newly created config file
+pub fn reference_comment() -> String {
+    let mut comment = String::from(
+        "# Remap keys by action name, e.g. next_post = [\"j\", \"Down\"] or quit = \"q\".\n\
+         # Keys: a single character, Enter, Esc, Tab, BackTab, Backspace, Space, Up, Down,\n\
+         # Left, Right, Home, End, PageUp, PageDown, Delete, F1-F12, with ctrl+ / alt+.\n\
+         # Actions and their defaults:\n",
+    );
+    for action in Action::ALL {
+        let mut keys: Vec<&str> = Vec::new();
+        for (_, defaults) in action.defaults() {
+            for key in defaults.iter() {
+                if !keys.contains(key) {
+                    keys.push(key);
+                }
+            }
+        }
+        let keys: Vec<String> = keys.iter().map(|k| format!("\"{}\"", k)).collect();
+        comment.push_str(&format!("# {} = [{}]\n", action.name(), keys.join(", ")));
+    }
+    comment
+}
@@ -1,6 +1,13 @@
-use std::{error::Error, io, time::Duration};
+use std::{
+    error::Error,
+    io,
+    time::{Duration, Instant},
+};
 use crossterm::{
-    event::{self, Event, KeyCode, EventStream},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event,
+        EventStream, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -13,98 +20,431 @@ mod categories;
 mod cli;
 mod config;
 mod db;
+mod diagnostics;
+mod export;
+mod fulltext;
 mod input;
+mod keymap;
+mod metrics;
 mod navigation;
+mod opml;
+mod reading;
+mod relative_time;
 mod rss;
 mod stats;
-mod tabs;
 mod theme;
 mod ui;
 
-use app::{App, ConfirmAction, InputMode};
+use app::{App, ConfirmAction, DiscoveredFeed, FeedCheck, InputMode};
 use cli::{Cli, Commands};
-use navigation::{FocusPane, NavNode, SidebarSection};
+use export::ExportFormat;
+use input::TextInput;
+use keymap::{Action, Context};
+use navigation::{FocusPane, NavNode, SidebarSection, SmartView};
 use std::sync::{Arc, Mutex};
 
-fn import_opml_content(content: &str, db: &Arc<Mutex<db::Database>>) -> usize {
-    let mut count = 0;
-    let mut current_category = "General".to_string();
+/// Feeds added per chunk before reporting progress and yielding
+const IMPORT_CHUNK_SIZE: usize = 20;
 
-    for line in content.lines() {
-        let trimmed = line.trim();
+/// Progress updates sent back to the UI loop by a background OPML import
+enum ImportEvent {
+    Progress { done: usize, total: usize },
+    /// `skipped` feeds were already subscribed
+    Finished { imported: usize, skipped: usize, total: usize },
+}
 
-        if trimmed.starts_with("<outline") && !trimmed.contains("xmlUrl") {
-            if let Some(start) = trimmed.find("text=\"") {
-                let rest = &trimmed[start + 6..];
-                if let Some(end) = rest.find('"') {
-                    current_category = rest[..end]
-                        .replace("&amp;", "&")
-                        .replace("&lt;", "<")
-                        .replace("&gt;", ">")
-                        .replace("&quot;", "\"")
-                        .to_string();
+/// Add OPML feeds in chunks so the UI keeps drawing during large imports.
+/// Feeds without a folder go into `default_category`.
+async fn import_opml_feeds(
+    db: Arc<Mutex<db::Database>>,
+    feeds: Vec<opml::OpmlFeed>,
+    default_category: Option<String>,
+    tx: tokio::sync::mpsc::Sender<ImportEvent>,
+) {
+    let total = feeds.len();
+    let mut imported = 0;
+    let mut skipped = 0;
+
+    for (i, chunk) in feeds.chunks(IMPORT_CHUNK_SIZE).enumerate() {
+        {
+            let db = db::lock_db(&db);
+            for feed in chunk {
+                if let Ok(Some(_)) = db.find_feed_by_url(&feed.url) {
+                    skipped += 1;
+                    continue;
                 }
-            }
-        }
-
-        if trimmed.contains("xmlUrl=") {
-            if let Some(start) = trimmed.find("xmlUrl=\"") {
-                let rest = &trimmed[start + 8..];
-                if let Some(end) = rest.find('"') {
-                    let url = &rest[..end];
-                    if let Ok(db) = db.lock() {
-                        if db.add_feed_with_category(url, &current_category).is_ok() {
-                            count += 1;
-                        }
+                // Outlines outside any folder go to Uncategorized, as `news import` does
+                let result = match feed.category.as_deref().or(default_category.as_deref()) {
+                    Some(category) => db.add_feed_with_category(&feed.url, category),
+                    None => db.add_feed(&feed.url),
+                };
+                if let Ok(id) = result {
+                    if let Some(title) = &feed.title {
+                        let _ = db.set_feed_title_if_missing(id, title);
                     }
+                    imported += 1;
                 }
             }
         }
+        let done = (i * IMPORT_CHUNK_SIZE + chunk.len()).min(total);
+        let _ = tx.send(ImportEvent::Progress { done, total }).await;
+        tokio::task::yield_now().await;
     }
-    count
+
+    let _ = tx.send(ImportEvent::Finished { imported, skipped, total }).await;
 }
 
-async fn fetch_feeds_for_node(
-    db: Arc<Mutex<db::Database>>,
+/// Download an OPML document. The content type is only checked loosely since
+/// plenty of servers label OPML as text/plain or octet-stream.
+async fn fetch_opml(url: &str) -> Result<String, Box<dyn Error>> {
+    let response = http_client().get(url).send().await?.error_for_status()?;
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_lowercase();
+    let body = response.text().await?;
+
+    let looks_like_xml = content_type.contains("xml") || content_type.contains("opml");
+    if !looks_like_xml && !body.contains("<opml") {
+        return Err(format!("{} doesn't look like OPML (content type '{}')", url, content_type).into());
+    }
+    Ok(body)
+}
+
+/// How often the background health checker wakes up
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+/// Feeds not fetched or checked for this long get probed by the health checker
+const HEALTH_STALE_AFTER_SECS: i64 = 60 * 60;
+/// Upper bound on feeds probed per wake-up so large lists are checked incrementally
+const HEALTH_CHECKS_PER_TICK: usize = 5;
+/// Feeds fetched at once during a refresh
+const FEED_FETCH_CONCURRENCY: usize = 4;
+/// How often the article auto-scroll advances
+const AUTO_SCROLL_TICK: Duration = Duration::from_millis(100);
+
+/// Sent back to the UI loop when a background fetch finishes
+struct FetchReport {
     node: NavNode,
-    tx: tokio::sync::mpsc::Sender<NavNode>,
-) {
-    let client = reqwest::Client::builder()
+    elapsed: Duration,
+    new_posts: usize,
+    failed: usize,
+}
+
+fn http_client() -> reqwest::Client {
+    http_client_builder().build().unwrap()
+}
+
+fn http_client_builder() -> reqwest::ClientBuilder {
+    reqwest::Client::builder()
         .timeout(Duration::from_secs(10))
         .user_agent("news-feed-tui/0.1")
-        .build()
-        .unwrap();
+}
 
+/// Client for sources with `allow_invalid_certs` set; never used otherwise
+fn insecure_http_client() -> reqwest::Client {
+    http_client_builder().tls_danger_accept_invalid_certs(true).build().unwrap()
+}
+
+async fn fetch_feeds_for_node(
+    db: Arc<Mutex<db::Database>>,
+    node: NavNode,
+    config: config::Config,
+    tx: tokio::sync::mpsc::Sender<FetchReport>,
+) {
+    let started = Instant::now();
     let feeds_list = {
-        let db = db.lock().unwrap();
+        let db = db::lock_db(&db);
         match &node {
             NavNode::SmartView(_) => db.get_feeds().unwrap_or_default(),
             NavNode::Category(cat) => db.get_feeds_by_category(cat).unwrap_or_default(),
         }
     };
 
+    let (new_posts, failed) = fetch_feeds(&db, feeds_list, &config, |_, _| {}).await;
+    let _ = tx
+        .send(FetchReport { node, elapsed: started.elapsed(), new_posts, failed })
+        .await;
+}
+
+/// Fetch `feeds_list` and store their new posts, returning the new post and
+/// failed feed totals. `on_feed` hears about each feed as it finishes, with
+/// its new post count or the error recorded for it.
+async fn fetch_feeds(
+    db: &Arc<Mutex<db::Database>>,
+    feeds_list: Vec<db::Feed>,
+    config: &config::Config,
+    mut on_feed: impl FnMut(&db::Feed, Result<usize, &str>),
+) -> (usize, usize) {
+    let client = http_client();
+    let insecure_client = insecure_http_client();
+    let mut new_posts = 0;
+    let mut failed = 0;
+
+    // URLs, tokens and cache headers are looked up first so the fetches below don't touch the database
+    let mut requests = Vec::new();
     for feed_meta in feeds_list {
-        match rss::fetch_feed(&client, &feed_meta.url).await {
-            Ok(feed_data) => {
-                let db = db.lock().unwrap();
+        let expanded = config::expand_env_vars(&feed_meta.url)
+            .and_then(|url| Ok((url, config.feeds.token_for(&feed_meta.url)?)));
+        let (url, token) = match expanded {
+            Ok(expanded) => expanded,
+            Err(e) => {
+                let _ = db::lock_db(db).record_feed_result(feed_meta.id, Err(&e));
+                on_feed(&feed_meta, Err(&e));
+                failed += 1;
+                continue;
+            }
+        };
+        let cache = db::lock_db(db).get_feed_cache_headers(feed_meta.id).unwrap_or_default();
+        requests.push((feed_meta, url, token, cache));
+    }
+
+    // Fetched a few at a time, so one feed waiting out its retries doesn't hold up the rest
+    let mut fetches = futures::stream::iter(requests)
+        .map(|(feed_meta, url, token, cache)| {
+            let client = if config.feeds.allows_invalid_certs(&feed_meta.url) {
+                &insecure_client
+            } else {
+                &client
+            };
+            let retries = config.feeds.fetch_retries;
+            let user_agent = config.feeds.browser_user_agent();
+            async move {
+                let mut attempt = 0;
+                let result = loop {
+                    match rss::fetch_feed_if_changed(client, &url, &cache, token.as_deref(), user_agent).await {
+                        Err(e) if e.is_transient() && attempt < retries => {
+                            tokio::time::sleep(rss::retry_delay(attempt)).await;
+                            attempt += 1;
+                        }
+                        result => break result,
+                    }
+                };
+                (feed_meta, result)
+            }
+        })
+        .buffer_unordered(FEED_FETCH_CONCURRENCY);
+
+    while let Some((feed_meta, result)) = fetches.next().await {
+        match result {
+            Ok(rss::FetchOutcome::NotModified) => {
+                let _ = db::lock_db(db).record_feed_result(feed_meta.id, Ok(()));
+                on_feed(&feed_meta, Ok(0));
+            }
+            Ok(rss::FetchOutcome::Fetched { feed: feed_data, cache }) => {
+                let db = db::lock_db(db);
+                let mut feed_new_posts = 0;
+                let _ = db.update_feed_cache_headers(feed_meta.id, &cache);
+                if config.feeds.use_feed_category
+                    && feed_meta.auto_category
+                    && let Some(category) = feed_data.categories.first()
+                {
+                    let name = category.label.clone().unwrap_or_else(|| category.term.clone());
+                    if !name.trim().is_empty() {
+                        let _ = db.update_feed_category(feed_meta.id, name.trim());
+                    }
+                }
+
+                if feed_meta.description.is_none()
+                    && let Some(description) = &feed_data.description
+                {
+                    let text = description.content.trim();
+                    if !text.is_empty() {
+                        let _ = db.set_feed_description_if_missing(feed_meta.id, text);
+                    }
+                }
+
                 for entry in feed_data.entries {
                     let title = entry.title.map(|t| t.content).unwrap_or_default();
-                    let url = entry.links.first().map(|l| l.href.clone()).unwrap_or_default();
-
+                    if config.filters.is_blocked(&title) {
+                        continue;
+                    }
+                    let enclosure_url = rss::enclosure_url(&entry.media);
+                    // Some podcast feeds link an episode only through its audio file
+                    let url = entry
+                        .links
+                        .first()
+                        .map(|l| l.href.clone())
+                        .or_else(|| enclosure_url.clone())
+                        .unwrap_or_default();
+
+                    let summary = entry.summary.map(|s| s.content).filter(|s| !s.trim().is_empty());
                     let mut content = entry.content.and_then(|c| c.body).unwrap_or_default();
                     if content.trim().is_empty() {
-                        content = entry.summary.map(|s| s.content).unwrap_or_default();
+                        content = summary.clone().unwrap_or_default();
                     }
 
                     let pub_date = entry.published.or(entry.updated);
-                    let _ = db.insert_post(feed_meta.id, &title, &url, Some(&content), pub_date);
+                    // The URL constraint stays the main guard; this catches the same story from another feed
+                    if config.feeds.skip_duplicate_posts && db.post_exists_similar(&title, pub_date).unwrap_or(false) {
+                        continue;
+                    }
+                    let inserted =
+                        db.insert_post(feed_meta.id, &title, &url, Some(&content), summary.as_deref(), pub_date);
+                    if let Ok(true) = inserted {
+                        feed_new_posts += 1;
+                    }
+                    if let Some(enclosure_url) = &enclosure_url {
+                        let _ = db.set_post_enclosure_if_missing(&url, enclosure_url);
+                    }
                 }
+                let _ = db.record_feed_result(feed_meta.id, Ok(()));
+                drop(db);
+                new_posts += feed_new_posts;
+                on_feed(&feed_meta, Ok(feed_new_posts));
+            }
+            Err(e) => {
+                let error = rss::describe_fetch_error(&e);
+                let _ = db::lock_db(db).record_feed_result(feed_meta.id, Err(&error));
+                on_feed(&feed_meta, Err(&error));
+                failed += 1;
             }
-            Err(_) => {}
         }
     }
+    (new_posts, failed)
+}
 
-    let _ = tx.send(node).await;
+/// Outcome of fetching a post's web page for its full text
+struct FullText {
+    post_id: i64,
+    result: Result<String, String>,
+    /// Fetched to compare with the saved copy rather than to replace it
+    live: bool,
+}
+
+/// Fetch the open article's web page in the background, at most
+/// `max_fulltext_fetches` at a time
+fn fetch_full_text(app: &mut App, tx: &tokio::sync::mpsc::Sender<FullText>, live: bool) {
+    let Some(post) = app.posts.get(app.selected_index) else {
+        return;
+    };
+    let (post_id, url) = (post.id, post.url.clone());
+    if !app.fulltext_loading.insert(post_id) {
+        return;
+    }
+    let permits = app.fulltext_permits.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let _permit = permits.acquire_owned().await;
+        let result = fulltext::fetch_article(&http_client(), &url)
+            .await
+            .map_err(|e| rss::describe_fetch_error(e.as_ref()));
+        let _ = tx.send(FullText { post_id, result, live }).await;
+    });
+}
+
+/// Check a URL typed into the add-feed prompt before anything is saved. For a
+/// web page, every discovered candidate that parses as a feed is offered.
+/// Requests go out as a refresh would send them, so a URL listed in a config
+/// source gets its token and certificate setting.
+async fn check_feed_url(
+    url: String,
+    feeds_config: config::FeedsConfig,
+    tx: tokio::sync::mpsc::Sender<FeedCheck>,
+) {
+    let client = if feeds_config.allows_invalid_certs(&url) {
+        insecure_http_client()
+    } else {
+        http_client()
+    };
+    let user_agent = feeds_config.browser_user_agent();
+    let token = match feeds_config.token_for(&url) {
+        Ok(token) => token,
+        Err(error) => {
+            let _ = tx.send(FeedCheck::Invalid { url, error }).await;
+            return;
+        }
+    };
+
+    let check = match rss::probe_feed(&client, &url, token.as_deref(), user_agent).await {
+        Ok(rss::Probe::Feed { title }) => FeedCheck::Valid { url, title },
+        Ok(rss::Probe::Page) => {
+            let mut found = Vec::new();
+            for candidate in rss::discover_feeds(&client, &url, user_agent).await {
+                let Ok(token) = feeds_config.token_for(&candidate) else {
+                    continue;
+                };
+                if let Ok(rss::Probe::Feed { title }) =
+                    rss::probe_feed(&client, &candidate, token.as_deref(), user_agent).await
+                {
+                    found.push(DiscoveredFeed { url: candidate, title });
+                }
+            }
+            if found.is_empty() {
+                FeedCheck::Invalid { url, error: "no feed found on that page".to_string() }
+            } else {
+                FeedCheck::Discovered(found)
+            }
+        }
+        Err(e) => FeedCheck::Invalid { url, error: rss::describe_fetch_error(&e) },
+    };
+    let _ = tx.send(check).await;
+}
+
+/// Low-priority loop that probes feeds which haven't been fetched recently and
+/// records their health, one feed at a time, independent of user refreshes.
+async fn run_feed_health_checks(
+    db: Arc<Mutex<db::Database>>,
+    feeds_config: config::FeedsConfig,
+    tx: tokio::sync::mpsc::Sender<()>,
+) {
+    let client = http_client();
+    let insecure_client = insecure_http_client();
+    let mut interval = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+    // The first tick completes immediately; the startup fetch already covers that.
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        let stale_feeds = db::lock_db(&db)
+            .get_stale_feeds(HEALTH_STALE_AFTER_SECS, HEALTH_CHECKS_PER_TICK)
+            .unwrap_or_default();
+
+        for feed in stale_feeds {
+            let client = if feeds_config.allows_invalid_certs(&feed.url) {
+                &insecure_client
+            } else {
+                &client
+            };
+            let expanded = config::expand_env_vars(&feed.url)
+                .and_then(|url| Ok((url, feeds_config.token_for(&feed.url)?)));
+            // The stored validators make an unchanged feed a cheap 304. New ones
+            // aren't saved: the posts weren't, so the next refresh must still fetch them.
+            let cache = db::lock_db(&db).get_feed_cache_headers(feed.id).unwrap_or_default();
+            let result = match expanded {
+                Ok((url, token)) => {
+                    rss::fetch_feed_if_changed(client, &url, &cache, token.as_deref(), feeds_config.browser_user_agent())
+                        .await
+                        .map(|_| ())
+                        .map_err(|e| rss::describe_fetch_error(&e))
+                }
+                Err(e) => Err(e),
+            };
+            let _ = db::lock_db(&db).record_feed_check(feed.id, result.as_ref().map(|_| ()).map_err(String::as_str));
+        }
+
+        if tx.send(()).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Delete old non-starred posts when `startup_cleanup` is on, returning the
+/// status bar note saying how many went
+fn startup_cleanup(db: &db::Database, app_config: &config::AppConfig) -> Option<String> {
+    if !app_config.startup_cleanup {
+        return None;
+    }
+    match db.cleanup_old_posts(app_config.cleanup_days) {
+        Ok(0) => None,
+        Ok(deleted) => Some(format!(
+            "Cleaned up {} posts older than {} days",
+            deleted, app_config.cleanup_days
+        )),
+        Err(e) => Some(format!("Startup cleanup failed: {}", e)),
+    }
 }
 
 #[tokio::main]
@@ -116,14 +456,14 @@ async fn main() -> Result<(), Box<dyn Error>> {
     }
 
     let config_path = cli.get_config_path();
-    let config = config::load_config_from_path(&config_path).unwrap_or_else(|e| {
-        eprintln!("Error loading config: {}. Using default.", e);
-        config::Config {
-            app: config::AppConfig::default(),
-            ui: config::UiConfig::default(),
-            feeds: config::FeedsConfig::default(),
+    let (config, config_note, unknown_keys) = match config::load_config_from_path(&config_path) {
+        Ok((config, unknown_keys)) => (config, None, unknown_keys),
+        Err(e) => {
+            let (config, note) = config::recover_config(&config_path, e.as_ref());
+            eprintln!("{}", note);
+            (config, Some(note), None)
         }
-    });
+    };
 
     let db_path = cli.get_db_path();
     let db = db::Database::init_with_path(&db_path)?;
@@ -141,78 +481,256 @@ async fn main() -> Result<(), Box<dyn Error>> {
         }
     }
 
-    let mut app = App::new(db);
+    let cleanup_note = startup_cleanup(&db, &config.app);
+
+    let mut app = App::new(db, config.clone());
+    if let Some(data_dir) = db_path.parent() {
+        app.data_dir = data_dir.to_path_buf();
+    }
+    app.config_path = config_path.clone();
+    app.config_recovered = config_note.is_some();
+    app.db_path = db_path.clone();
+    app.color_mode = cli.color.unwrap_or_else(theme::ColorMode::detect);
+    if let Some(theme_name) = &cli.theme {
+        app.theme = theme::ThemeVariant::from_str(theme_name);
+    }
+    if cli.verbose {
+        app.debug = Some(metrics::DebugMetrics::default());
+    }
+    if config_note.is_some() {
+        app.message = config_note;
+    } else if unknown_keys.is_some() {
+        app.message = unknown_keys;
+    } else if cleanup_note.is_some() {
+        app.message = cleanup_note;
+    }
     let db_clone = app.db.clone();
 
-    let (tx, mut rx) = tokio::sync::mpsc::channel::<NavNode>(10);
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<FetchReport>(10);
+    let (health_tx, mut health_rx) = tokio::sync::mpsc::channel::<()>(1);
+    let (import_tx, mut import_rx) = tokio::sync::mpsc::channel::<ImportEvent>(10);
+    let (check_tx, mut check_rx) = tokio::sync::mpsc::channel::<FeedCheck>(1);
+    let (fulltext_tx, mut fulltext_rx) = tokio::sync::mpsc::channel::<FullText>(10);
 
-    if !app.feeds.is_empty() {
+    if !cli.no_auto_update {
+        let db_for_health = db_clone.clone();
+        let feeds_config = app.config.feeds.clone();
+        tokio::spawn(async move {
+            run_feed_health_checks(db_for_health, feeds_config, health_tx).await;
+        });
+    }
+
+    if cli.no_auto_update {
+        // Posts come from the database only until the user refreshes
+        app.is_loading = false;
+        if app.message.is_none() && !app.feeds.is_empty() {
+            app.message = Some("Auto-update disabled; press r to refresh".to_string());
+        }
+    } else if !app.feeds.is_empty() {
         let db_for_fetch = db_clone.clone();
         let tx_clone = tx.clone();
         let initial_node = app.active_node.clone();
+        let config_clone = app.config.clone();
         tokio::spawn(async move {
-            fetch_feeds_for_node(db_for_fetch, initial_node, tx_clone).await;
+            fetch_feeds_for_node(db_for_fetch, initial_node, config_clone, tx_clone).await;
         });
     }
 
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     let mut reader = EventStream::new();
-    let theme_name = cli.theme.clone().unwrap_or_else(|| config.app.theme.clone());
+    let mut spinner_tick = tokio::time::interval(Duration::from_millis(100));
+    let mut auto_scroll_tick = tokio::time::interval(AUTO_SCROLL_TICK);
+    // The branch is disabled while paused; don't replay those ticks on resume
+    auto_scroll_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let auto_refresh = !cli.no_auto_update && app.config.app.refresh_interval_secs > 0;
+    let refresh_period = Duration::from_secs(app.config.app.refresh_interval_secs.max(1));
+    let mut refresh_tick =
+        tokio::time::interval_at(tokio::time::Instant::now() + refresh_period, refresh_period);
+    refresh_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    // Whether the fetch in flight was started by `refresh_tick` rather than the user
+    let mut background_refresh = false;
 
     loop {
-        terminal.draw(|f| ui::ui(f, &mut app, &theme_name))?;
+        if db::take_lock_recovered() {
+            app.message = Some("A background task crashed while using the database; recovered".to_string());
+        }
+        let frame_start = Instant::now();
+        terminal.draw(|f| ui::ui(f, &mut app))?;
+        if let Some(metrics) = app.debug.as_mut() {
+            metrics.frame_time = frame_start.elapsed();
+        }
 
         tokio::select! {
-            Some(fetched_node) = rx.recv() => {
+            Some(report) = rx.recv() => {
+                let fetched_node = report.node;
+                if let Some(metrics) = app.debug.as_mut() {
+                    metrics.last_fetch = Some(report.elapsed);
+                }
                 app.sidebar.mark_fetched(fetched_node.clone());
                 if app.active_node == fetched_node {
                     app.reload_posts_for_active_node();
                 }
                 app.refresh_sidebar();
                 app.is_loading = false;
-                app.message = Some("Feeds updated".to_string());
+                // A message swallows the next key, so a quiet background refresh stays quiet
+                if std::mem::take(&mut background_refresh) && report.new_posts == 0 {
+                    continue;
+                }
+                let mut message = match report.new_posts {
+                    0 => "No new posts".to_string(),
+                    1 => "1 new post".to_string(),
+                    n => format!("{} new posts", n),
+                };
+                if report.failed > 0 {
+                    message.push_str(&format!(" · {} feeds failed (see e:Edit Feeds)", report.failed));
+                }
+                app.message = Some(message);
             }
-            Some(Ok(event)) = reader.next() => {
+            Some(()) = health_rx.recv() => {
+                app.refresh_feed_health();
+            }
+            Some(check) = check_rx.recv() => {
+                app.handle_feed_check(check);
+            }
+            Some(FullText { post_id, result, live }) = fulltext_rx.recv() => {
+                app.apply_full_text(post_id, result, live);
+            }
+            Some(event) = import_rx.recv() => {
                 match event {
-                    Event::Key(key) => {
-                        if key.kind == event::KeyEventKind::Press {
-                            if app.message.is_some() && !matches!(app.input_mode, InputMode::Confirming(_)) {
-                                app.message = None;
-                                continue;
+                    ImportEvent::Progress { done, total } => {
+                        app.import_progress = Some((done, total));
+                    }
+                    ImportEvent::Finished { imported, skipped, total } => {
+                        app.import_progress = None;
+                        app.reload_feeds();
+                        app.refresh_sidebar();
+                        let mut summary = format!("Imported {} of {} feeds", imported, total);
+                        if skipped > 0 {
+                            summary.push_str(&format!(", skipped {} already subscribed", skipped));
+                        }
+                        let failed = total - imported - skipped;
+                        if failed > 0 {
+                            summary.push_str(&format!(", {} couldn't be added", failed));
+                        }
+                        app.message = Some(summary);
+                        if let InputMode::EditingCategoryFeeds(cat) = &app.input_mode {
+                            let cat = cat.clone();
+                            app.load_category_feeds(&cat);
+                        }
+
+                        if imported > 0 {
+                            app.is_loading = true;
+                            if app.input_mode == InputMode::Welcome {
+                                app.input_mode = InputMode::Normal;
                             }
 
-                            match &app.input_mode {
-                                InputMode::Welcome => {
-                                    handle_welcome_input(&mut app, key.code, &tx, &db_clone);
-                                }
-                                InputMode::Help => {
-                                    app.input_mode = InputMode::Normal;
-                                }
-                                InputMode::AddingFeed => {
-                                    handle_adding_feed_input(&mut app, key.code);
-                                }
-                                InputMode::AddingCategory => {
-                                    handle_adding_category_input(&mut app, key.code);
-                                }
-                                InputMode::SelectingCategory => {
-                                    handle_selecting_category_input(&mut app, key.code);
-                                }
-                                InputMode::Confirming(action) => {
-                                    let action_clone = action.clone();
-                                    handle_confirm_input(&mut app, key.code, action_clone);
-                                }
-                                InputMode::EditingCategoryFeeds(cat) => {
-                                    let cat_clone = cat.clone();
-                                    handle_editing_category_feeds_input(&mut app, key.code, &cat_clone);
-                                }
-                                InputMode::Normal => {
-                                    handle_normal_input(&mut app, key.code, &tx, &db_clone);
-                                }
+                            let db_for_fetch = db_clone.clone();
+                            let tx_clone = tx.clone();
+                            let node = app.active_node.clone();
+                            let config = app.config.clone();
+                            tokio::spawn(async move {
+                                fetch_feeds_for_node(db_for_fetch, node, config, tx_clone).await;
+                            });
+                        }
+                    }
+                }
+            }
+            _ = spinner_tick.tick(), if app.is_loading || app.import_progress.is_some() => {
+                app.spinner_frame = app.spinner_frame.wrapping_add(1);
+            }
+            _ = refresh_tick.tick(), if auto_refresh && !app.is_loading && !app.feeds.is_empty() => {
+                app.is_loading = true;
+                background_refresh = true;
+                let db_for_fetch = db_clone.clone();
+                let tx_clone = tx.clone();
+                let node = app.active_node.clone();
+                let config = app.config.clone();
+                tokio::spawn(async move {
+                    fetch_feeds_for_node(db_for_fetch, node, config, tx_clone).await;
+                });
+            }
+            _ = auto_scroll_tick.tick(), if app.is_auto_scrolling() => {
+                app.advance_auto_scroll(AUTO_SCROLL_TICK);
+            }
+            Some(Ok(event)) = reader.next() => {
+                match event {
+                    Event::Key(key) if key.kind == event::KeyEventKind::Press => {
+                        if app.message.is_some() && !matches!(app.input_mode, InputMode::Confirming(_)) {
+                            app.message = None;
+                            continue;
+                        }
+
+                        match &app.input_mode {
+                            InputMode::Welcome => {
+                                handle_welcome_input(&mut app, key.code);
+                            }
+                            InputMode::ImportingOpml(origin) => {
+                                let origin = origin.clone();
+                                handle_importing_opml_input(&mut app, key, origin, &import_tx, &db_clone);
+                            }
+                            InputMode::Dashboard if key.code == KeyCode::Char('b') => {
+                                app.show_banner = !app.show_banner;
+                            }
+                            InputMode::Help | InputMode::Dashboard => {
+                                app.input_mode = InputMode::Normal;
+                            }
+                            InputMode::AddingFeed => {
+                                handle_adding_feed_input(&mut app, key, &check_tx);
+                            }
+                            InputMode::AddingCategory => {
+                                handle_adding_category_input(&mut app, key);
+                            }
+                            InputMode::Searching => {
+                                handle_searching_input(&mut app, key);
+                            }
+                            InputMode::Filtering => {
+                                handle_filtering_input(&mut app, key);
+                            }
+                            InputMode::SelectingCategory => {
+                                handle_selecting_category_input(&mut app, key.code);
+                            }
+                            InputMode::ChoosingDiscoveredFeed => {
+                                handle_choosing_discovered_feed_input(&mut app, key.code);
+                            }
+                            InputMode::MovingCategoryFeeds(from) => {
+                                let from_clone = from.clone();
+                                handle_moving_category_feeds_input(&mut app, key.code, &from_clone);
+                            }
+                            InputMode::Confirming(action) => {
+                                let action_clone = action.clone();
+                                handle_confirm_input(&mut app, key.code, action_clone);
+                            }
+                            InputMode::EditingCategoryFeeds(cat) => {
+                                let cat_clone = cat.clone();
+                                handle_editing_category_feeds_input(&mut app, key.code, &cat_clone);
+                            }
+                            InputMode::Normal => {
+                                handle_normal_input(&mut app, key, &tx, &fulltext_tx, &db_clone);
+                            }
+                        }
+                    }
+                    Event::Mouse(mouse) if app.input_mode == InputMode::Normal => {
+                        handle_mouse_input(&mut app, mouse);
+                    }
+                    Event::Paste(text) => {
+                        if matches!(
+                            app.input_mode,
+                            InputMode::AddingFeed
+                                | InputMode::AddingCategory
+                                | InputMode::Searching
+                                | InputMode::Filtering
+                                | InputMode::ImportingOpml(_)
+                        ) {
+                            // The prompts are single-line; a copied URL often ends in a newline
+                            let text: String = text.chars().filter(|c| *c != '\n' && *c != '\r').collect();
+                            app.text_input.insert_str(&text);
+                            if app.input_mode == InputMode::Filtering {
+                                app.apply_list_filter();
                             }
                         }
                     }
@@ -227,73 +745,116 @@ async fn main() -> Result<(), Box<dyn Error>> {
     }
 
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(terminal.backend_mut(), DisableMouseCapture, DisableBracketedPaste, LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
     Ok(())
 }
 
-fn handle_welcome_input(
-    app: &mut App,
-    key: KeyCode,
-    tx: &tokio::sync::mpsc::Sender<NavNode>,
-    db: &Arc<Mutex<db::Database>>,
-) {
+fn handle_welcome_input(app: &mut App, key: KeyCode) {
     match key {
         KeyCode::Char('q') => app.exit = true,
         KeyCode::Char('a') => {
             app.input_mode = InputMode::AddingFeed;
         }
-        KeyCode::Char('i') => {
-            let home = std::env::var("HOME").unwrap_or_default();
-            let opml_paths = vec![
-                format!("{}/Downloads/feeds_organized.opml", home),
-                format!("{}/Downloads/feeds.opml", home),
-                format!("{}/feeds.opml", home),
-            ];
+        KeyCode::Char('i') if app.import_progress.is_none() => {
+            app.start_opml_import(None);
+        }
+        _ => {}
+    }
+}
 
-            let mut imported = 0;
-            for path in opml_paths {
-                if std::path::Path::new(&path).exists() {
-                    if let Ok(content) = std::fs::read_to_string(&path) {
-                        imported += import_opml_content(&content, &app.db);
-                    }
-                    break;
-                }
+/// Path prompt for an OPML import. A file that can't be read or parsed keeps
+/// the prompt open with the error shown in it.
+fn handle_importing_opml_input(
+    app: &mut App,
+    key: KeyEvent,
+    origin: Option<String>,
+    import_tx: &tokio::sync::mpsc::Sender<ImportEvent>,
+    db: &Arc<Mutex<db::Database>>,
+) {
+    let back = match &origin {
+        Some(cat) => InputMode::EditingCategoryFeeds(cat.clone()),
+        None => InputMode::Welcome,
+    };
+    if handle_text_edit_key(&mut app.text_input, key) {
+        app.import_error = None;
+        return;
+    }
+    match key.code {
+        KeyCode::Enter => {
+            if app.import_progress.is_some() {
+                return;
             }
+            let Some(feeds) = app.read_opml_import() else {
+                return;
+            };
+            app.import_progress = Some((0, feeds.len()));
+            app.text_input.clear();
+            app.input_mode = back;
 
-            if imported > 0 {
-                app.reload_feeds();
-                app.refresh_sidebar();
-                app.is_loading = true;
-                app.input_mode = InputMode::Normal;
-                app.message = Some(format!("Imported {} feeds!", imported));
-
-                let db_clone = db.clone();
-                let tx_clone = tx.clone();
-                let node = app.active_node.clone();
-                tokio::spawn(async move {
-                    fetch_feeds_for_node(db_clone, node, tx_clone).await;
-                });
-            } else {
-                app.message = Some("No OPML file found in ~/Downloads".to_string());
-            }
+            let db_clone = db.clone();
+            let import_tx = import_tx.clone();
+            tokio::spawn(async move {
+                import_opml_feeds(db_clone, feeds, origin, import_tx).await;
+            });
+        }
+        KeyCode::Char(c) => {
+            app.text_input.insert_char(c);
+            app.import_error = None;
+        }
+        KeyCode::Backspace => {
+            app.text_input.delete_char();
+            app.import_error = None;
+        }
+        KeyCode::Left => app.text_input.move_cursor_left(),
+        KeyCode::Right => app.text_input.move_cursor_right(),
+        KeyCode::Esc => {
+            app.text_input.clear();
+            app.import_error = None;
+            app.input_mode = back;
         }
         _ => {}
     }
 }
 
-fn handle_adding_feed_input(app: &mut App, key: KeyCode) {
-    match key {
+/// Word-wise editing keys shared by the text prompts. Returns whether the key
+/// was handled.
+fn handle_text_edit_key(input: &mut TextInput, key: KeyEvent) -> bool {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    match key.code {
+        KeyCode::Char('w') if ctrl => input.delete_word(),
+        KeyCode::Char('u') if ctrl => input.delete_to_start(),
+        KeyCode::Left if ctrl || alt => input.move_word_left(),
+        KeyCode::Right if ctrl || alt => input.move_word_right(),
+        KeyCode::Char('b') if alt => input.move_word_left(),
+        KeyCode::Char('f') if alt => input.move_word_right(),
+        _ => return false,
+    }
+    true
+}
+
+fn handle_adding_feed_input(app: &mut App, key: KeyEvent, check_tx: &tokio::sync::mpsc::Sender<FeedCheck>) {
+    if handle_text_edit_key(&mut app.text_input, key) {
+        return;
+    }
+    match key.code {
         KeyCode::Char(c) => app.text_input.insert_char(c),
         KeyCode::Backspace => app.text_input.delete_char(),
         KeyCode::Left => app.text_input.move_cursor_left(),
         KeyCode::Right => app.text_input.move_cursor_right(),
         KeyCode::Enter => {
-            if !app.text_input.value.is_empty() {
-                app.pending_feed_url = Some(app.text_input.value.clone());
+            let input = app.text_input.value.clone();
+            if let Some(url) = app.resolve_feed_url(&input) {
                 app.text_input.clear();
-                app.input_mode = InputMode::SelectingCategory;
+                app.input_mode = InputMode::Normal;
+                app.message = Some(format!("Checking {}…", url));
+                let tx = check_tx.clone();
+                let feeds_config = app.config.feeds.clone();
+                tokio::spawn(async move {
+                    check_feed_url(url, feeds_config, tx).await;
+                });
             }
         }
         KeyCode::Esc => {
@@ -305,18 +866,19 @@ fn handle_adding_feed_input(app: &mut App, key: KeyCode) {
     }
 }
 
-fn handle_adding_category_input(app: &mut App, key: KeyCode) {
-    match key {
+fn handle_adding_category_input(app: &mut App, key: KeyEvent) {
+    if handle_text_edit_key(&mut app.text_input, key) {
+        return;
+    }
+    match key.code {
         KeyCode::Char(c) => app.text_input.insert_char(c),
         KeyCode::Backspace => app.text_input.delete_char(),
         KeyCode::Left => app.text_input.move_cursor_left(),
         KeyCode::Right => app.text_input.move_cursor_right(),
-        KeyCode::Enter => {
-            if !app.text_input.value.is_empty() {
-                app.add_category(&app.text_input.value.clone());
-                app.text_input.clear();
-                app.input_mode = InputMode::Normal;
-            }
+        KeyCode::Enter if !app.text_input.value.is_empty() => {
+            app.add_category(&app.text_input.value.clone());
+            app.text_input.clear();
+            app.input_mode = InputMode::Normal;
         }
         KeyCode::Esc => {
             app.text_input.clear();
@@ -326,27 +888,119 @@ fn handle_adding_category_input(app: &mut App, key: KeyCode) {
     }
 }
 
+fn handle_searching_input(app: &mut App, key: KeyEvent) {
+    if handle_text_edit_key(&mut app.text_input, key) {
+        return;
+    }
+    match key.code {
+        KeyCode::Char(c) => app.text_input.insert_char(c),
+        KeyCode::Backspace => app.text_input.delete_char(),
+        KeyCode::Left => app.text_input.move_cursor_left(),
+        KeyCode::Right => app.text_input.move_cursor_right(),
+        KeyCode::Enter if !app.text_input.value.trim().is_empty() => {
+            app.search(&app.text_input.value.clone());
+            app.text_input.clear();
+            app.input_mode = InputMode::Normal;
+        }
+        KeyCode::Esc => {
+            app.text_input.clear();
+            app.input_mode = InputMode::Normal;
+        }
+        _ => {}
+    }
+}
+
+fn handle_filtering_input(app: &mut App, key: KeyEvent) {
+    if !handle_text_edit_key(&mut app.text_input, key) {
+        match key.code {
+            KeyCode::Char(c) => app.text_input.insert_char(c),
+            KeyCode::Backspace => app.text_input.delete_char(),
+            KeyCode::Left => app.text_input.move_cursor_left(),
+            KeyCode::Right => app.text_input.move_cursor_right(),
+            KeyCode::Enter => {
+                // Keep the narrowed list; Esc in the posts list clears it later
+                app.text_input.clear();
+                app.input_mode = InputMode::Normal;
+                if app.list_filter.is_none() {
+                    app.clear_list_filter();
+                }
+                return;
+            }
+            KeyCode::Esc => {
+                app.text_input.clear();
+                app.input_mode = InputMode::Normal;
+                app.clear_list_filter();
+                return;
+            }
+            _ => return,
+        }
+    }
+    app.apply_list_filter();
+}
+
 fn handle_selecting_category_input(app: &mut App, key: KeyCode) {
     match key {
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.sidebar.category_index < app.sidebar.categories.len().saturating_sub(1) {
-                app.sidebar.category_index += 1;
-            }
+        KeyCode::Down | KeyCode::Char('j')
+            if app.sidebar.category_index < app.sidebar.categories.len().saturating_sub(1) =>
+        {
+            app.sidebar.category_index += 1;
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.sidebar.category_index > 0 {
-                app.sidebar.category_index -= 1;
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.sidebar.category_index > 0 => {
+            app.sidebar.category_index -= 1;
         }
         KeyCode::Enter => {
             if let Some(url) = app.pending_feed_url.take() {
                 let category = app.get_selected_category();
-                app.add_feed(&url, &category);
+                // add_feed may switch to a move-feed confirmation for duplicates
                 app.input_mode = InputMode::Normal;
+                let title = app.pending_feed_title.take();
+                app.add_feed(&url, &category, title.as_deref());
             }
         }
         KeyCode::Esc => {
             app.pending_feed_url = None;
+            app.pending_feed_title = None;
+            app.input_mode = InputMode::Normal;
+        }
+        _ => {}
+    }
+}
+
+fn handle_choosing_discovered_feed_input(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Down | KeyCode::Char('j')
+            if app.discovered_feed_index < app.discovered_feeds.len().saturating_sub(1) =>
+        {
+            app.discovered_feed_index += 1;
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.discovered_feed_index = app.discovered_feed_index.saturating_sub(1);
+        }
+        KeyCode::Enter => app.choose_discovered_feed(),
+        KeyCode::Esc => {
+            app.discovered_feeds.clear();
+            app.input_mode = InputMode::Normal;
+        }
+        _ => {}
+    }
+}
+
+fn handle_moving_category_feeds_input(app: &mut App, key: KeyCode, from: &str) {
+    match key {
+        KeyCode::Down | KeyCode::Char('j')
+            if app.sidebar.category_index < app.sidebar.categories.len().saturating_sub(1) =>
+        {
+            app.sidebar.category_index += 1;
+        }
+        KeyCode::Up | KeyCode::Char('k') if app.sidebar.category_index > 0 => {
+            app.sidebar.category_index -= 1;
+        }
+        KeyCode::Enter => {
+            let to = app.get_selected_category();
+            app.input_mode = InputMode::Normal;
+            app.move_category_feeds(from, &to);
+        }
+        KeyCode::Esc => {
             app.input_mode = InputMode::Normal;
         }
         _ => {}
@@ -356,34 +1010,47 @@ fn handle_selecting_category_input(app: &mut App, key: KeyCode) {
 fn handle_confirm_input(app: &mut App, key: KeyCode, action: ConfirmAction) {
     match key {
         KeyCode::Char('y') | KeyCode::Char('Y') => {
+            // Arms that lead into another prompt set their own mode
+            app.input_mode = InputMode::Normal;
             match action {
-                ConfirmAction::DeletePost(id) => {
-                    if app.db.lock().unwrap().delete_post(id).is_ok() {
-                        app.posts.retain(|p| p.id != id);
-                        if app.selected_index >= app.posts.len() && !app.posts.is_empty() {
-                            app.selected_index = app.posts.len() - 1;
-                        }
+                ConfirmAction::DeletePost(id) => app.delete_post(id),
+                ConfirmAction::DeleteFeed(id) => {
+                    if db::lock_db(&app.db).delete_feed(id).is_ok() {
+                        app.reload_feeds();
                         app.refresh_sidebar();
-                        app.message = Some("Post deleted".to_string());
+                        app.reload_posts_for_active_node();
+                        app.message = Some("Feed deleted".to_string());
                     }
                 }
-                ConfirmAction::DeleteFeed(id) => {
-                    if app.db.lock().unwrap().delete_feed(id).is_ok() {
+                ConfirmAction::MoveFeed { feed_id, to, .. } => {
+                    if db::lock_db(&app.db).update_feed_category(feed_id, &to).is_ok() {
                         app.reload_feeds();
                         app.refresh_sidebar();
                         app.reload_posts_for_active_node();
-                        app.message = Some("Feed deleted".to_string());
+                        app.message = Some(format!("Moved feed to '{}'", to));
+                    }
+                }
+                ConfirmAction::DeleteCategoryPosts(name) => {
+                    let result = db::lock_db(&app.db).delete_posts_by_category(&name, true);
+                    if let Ok(deleted) = result {
+                        app.refresh_sidebar();
+                        app.reload_posts_for_active_node();
+                        app.message = Some(format!("Deleted {} posts from '{}'", deleted, name));
                     }
                 }
                 ConfirmAction::DeleteCategory(name) => {
-                    if app.db.lock().unwrap().delete_category(&name).is_ok() {
+                    if db::lock_db(&app.db).delete_category(&name).is_ok() {
                         app.refresh_sidebar();
                         app.reload_posts_for_active_node();
                         app.message = Some(format!("Category '{}' deleted", name));
                     }
                 }
+                ConfirmAction::UseDiscoveredFeed { url, title } => {
+                    app.pending_feed_url = Some(url);
+                    app.pending_feed_title = title;
+                    app.input_mode = InputMode::SelectingCategory;
+                }
             }
-            app.input_mode = InputMode::Normal;
         }
         KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
             app.input_mode = InputMode::Normal;
@@ -395,23 +1062,54 @@ fn handle_confirm_input(app: &mut App, key: KeyCode, action: ConfirmAction) {
 
 fn handle_normal_input(
     app: &mut App,
-    key: KeyCode,
-    tx: &tokio::sync::mpsc::Sender<NavNode>,
+    key: KeyEvent,
+    tx: &tokio::sync::mpsc::Sender<FetchReport>,
+    fulltext_tx: &tokio::sync::mpsc::Sender<FullText>,
     db: &Arc<Mutex<db::Database>>,
 ) {
-    match key {
-        KeyCode::Char('q') | KeyCode::Char('Q') => app.exit = true,
-        KeyCode::Char('?') => app.input_mode = InputMode::Help,
-        KeyCode::Char('h') | KeyCode::Left => app.focus_left(),
-        KeyCode::Char('l') | KeyCode::Right => app.focus_right(),
-        KeyCode::Tab => {
+    let context = match app.focus {
+        FocusPane::Sidebar => Context::Sidebar,
+        FocusPane::Posts => Context::Posts,
+        FocusPane::Article => Context::Article,
+    };
+    let Some(action) = app.keymap.action(context, key) else {
+        return;
+    };
+
+    match action {
+        Action::Quit => app.exit = true,
+        Action::Help => app.input_mode = InputMode::Help,
+        Action::Dashboard => app.open_dashboard(),
+        Action::CopyDiagnostics => app.copy_diagnostics_to_clipboard(),
+        Action::OpenDataDirs => app.open_data_dirs(),
+        Action::ReloadConfig => app.reload_config(),
+        Action::CycleTheme => app.cycle_theme(),
+        Action::UndoDelete => app.undo_delete(),
+        Action::Search => {
+            app.text_input.clear();
+            app.input_mode = InputMode::Searching;
+        }
+        Action::ToggleDebug => {
+            if let Some(metrics) = app.debug.as_mut() {
+                metrics.toggle();
+            }
+        }
+        Action::ViewFresh => app.open_smart_view(SmartView::Fresh),
+        Action::ViewAllFeeds => app.open_smart_view(SmartView::AllFeeds),
+        Action::ViewStarred => app.open_smart_view(SmartView::Starred),
+        Action::ViewReadLater => app.open_smart_view(SmartView::ReadLater),
+        Action::ViewArchived => app.open_smart_view(SmartView::Archived),
+        Action::FeedManager => app.open_feed_manager(),
+        Action::FocusLeft => app.focus_left(),
+        Action::FocusRight => app.focus_right(),
+        Action::NextPane => {
             app.focus = match app.focus {
                 FocusPane::Sidebar => FocusPane::Posts,
                 FocusPane::Posts => FocusPane::Sidebar,
                 FocusPane::Article => FocusPane::Sidebar,
             };
         }
-        KeyCode::BackTab => {
+        Action::PreviousPane => {
             app.focus = match app.focus {
                 FocusPane::Sidebar => FocusPane::Posts,
                 FocusPane::Posts => FocusPane::Sidebar,
@@ -419,43 +1117,51 @@ fn handle_normal_input(
             };
         }
         _ => match app.focus {
-            FocusPane::Sidebar => handle_sidebar_input(app, key),
-            FocusPane::Posts => handle_posts_input(app, key, tx, db),
-            FocusPane::Article => handle_article_input(app, key),
+            FocusPane::Sidebar => handle_sidebar_input(app, action),
+            FocusPane::Posts => handle_posts_input(app, action, tx, db),
+            FocusPane::Article => handle_article_input(app, action, fulltext_tx),
         },
     }
 }
 
-fn handle_sidebar_input(app: &mut App, key: KeyCode) {
-    match key {
-        KeyCode::Down | KeyCode::Char('j') => app.sidebar.next(),
-        KeyCode::Up | KeyCode::Char('k') => app.sidebar.previous(),
-        KeyCode::Enter => app.select_sidebar_item(),
-        KeyCode::Char('a') | KeyCode::Char('+') => {
+fn handle_sidebar_input(app: &mut App, action: Action) {
+    match action {
+        Action::NextItem => app.sidebar.next(),
+        Action::PreviousItem => app.sidebar.previous(),
+        Action::SelectItem => app.select_sidebar_item(),
+        Action::AddFeed => {
             // Always add feed - will prompt for category selection
             app.input_mode = InputMode::AddingFeed;
         }
-        KeyCode::Char('n') => {
+        Action::AddCategory => {
             // Add new category
             app.input_mode = InputMode::AddingCategory;
         }
-        KeyCode::Char('e') => {
+        Action::EditFeeds => {
             // Edit category feeds
-            if let SidebarSection::Categories = app.sidebar.section {
-                if let Some(cat) = app.sidebar.categories.get(app.sidebar.category_index).cloned() {
-                    app.load_category_feeds(&cat);
-                    app.input_mode = InputMode::EditingCategoryFeeds(cat);
-                }
+            if let SidebarSection::Categories = app.sidebar.section
+                && let Some(cat) = app.sidebar.categories.get(app.sidebar.category_index).cloned()
+            {
+                app.load_category_feeds(&cat);
+                app.input_mode = InputMode::EditingCategoryFeeds(cat);
             }
         }
-        KeyCode::Char('d') => {
-            if let SidebarSection::Categories = app.sidebar.section {
-                if let Some(cat) = app.sidebar.categories.get(app.sidebar.category_index).cloned() {
-                    if cat == "General" {
-                        app.message = Some("Cannot delete 'General' category".to_string());
-                    } else {
-                        app.input_mode = InputMode::Confirming(ConfirmAction::DeleteCategory(cat));
-                    }
+        Action::MoveFeeds => {
+            // Move all feeds in this category elsewhere
+            if let SidebarSection::Categories = app.sidebar.section
+                && let Some(cat) = app.sidebar.categories.get(app.sidebar.category_index).cloned()
+            {
+                app.input_mode = InputMode::MovingCategoryFeeds(cat);
+            }
+        }
+        Action::DeleteCategory => {
+            if let SidebarSection::Categories = app.sidebar.section
+                && let Some(cat) = app.sidebar.categories.get(app.sidebar.category_index).cloned()
+            {
+                if cat == "General" {
+                    app.message = Some("Cannot delete 'General' category".to_string());
+                } else {
+                    app.input_mode = InputMode::Confirming(ConfirmAction::DeleteCategory(cat));
                 }
             }
         }
@@ -467,6 +1173,9 @@ fn handle_editing_category_feeds_input(app: &mut App, key: KeyCode, category: &s
     match key {
         KeyCode::Down | KeyCode::Char('j') => app.next_category_feed(),
         KeyCode::Up | KeyCode::Char('k') => app.previous_category_feed(),
+        KeyCode::Char('s') => app.cycle_feed_sort(),
+        KeyCode::Char('U') => app.undo_delete(),
+        KeyCode::Char('i') if app.import_progress.is_none() => app.start_opml_import(Some(category.to_string())),
         KeyCode::Char('d') => {
             app.delete_category_feed();
             if app.category_feeds.is_empty() {
@@ -494,78 +1203,161 @@ fn handle_editing_category_feeds_input(app: &mut App, key: KeyCode, category: &s
 
 fn handle_posts_input(
     app: &mut App,
-    key: KeyCode,
-    tx: &tokio::sync::mpsc::Sender<NavNode>,
+    action: Action,
+    tx: &tokio::sync::mpsc::Sender<FetchReport>,
     db: &Arc<Mutex<db::Database>>,
 ) {
-    match key {
-        KeyCode::Down | KeyCode::Char('j') => app.next_post(),
-        KeyCode::Up | KeyCode::Char('k') => app.previous_post(),
-        KeyCode::Enter => app.open_article(),
-        KeyCode::Char('b') => app.toggle_bookmark(),
-        KeyCode::Char('l') => app.toggle_read_later(),
-        KeyCode::Char('a') => app.toggle_archived(),
-        KeyCode::Char('m') => app.toggle_read(),
-        KeyCode::Char('u') => app.toggle_show_read(),
-        KeyCode::Char('d') => {
+    match action {
+        Action::NextPost => app.next_post(),
+        Action::PreviousPost => app.previous_post(),
+        Action::Top => app.select_first_post(),
+        Action::Bottom => app.select_last_post(),
+        Action::HalfPageDown => app.half_page_posts(true),
+        Action::HalfPageUp => app.half_page_posts(false),
+        Action::OpenPost => app.open_article(),
+        Action::ToggleBookmark => app.toggle_bookmark(),
+        Action::ToggleReadLater => app.toggle_read_later(),
+        Action::ToggleArchived => app.toggle_archived(),
+        Action::ToggleRead => app.toggle_read(),
+        Action::ToggleShowRead => app.toggle_show_read(),
+        Action::CycleFilter => app.cycle_sub_filter(),
+        Action::CycleSort => app.cycle_sort_mode(),
+        Action::ClearSearch if app.list_filter.is_some() => app.clear_list_filter(),
+        Action::ClearSearch => app.clear_search(),
+        Action::FilterList => app.start_list_filter(),
+        Action::QueueUp => app.move_read_later(-1),
+        Action::QueueDown => app.move_read_later(1),
+        Action::DeletePost => {
             if let Some(post) = app.posts.get(app.selected_index) {
                 app.input_mode = InputMode::Confirming(ConfirmAction::DeletePost(post.id));
             }
         }
-        KeyCode::Char('o') => {
-            if let Some(post) = app.posts.get(app.selected_index) {
-                let _ = open::that(&post.url);
-                app.message = Some("Opened in browser".to_string());
+        Action::DeleteCategoryPosts => {
+            if let NavNode::Category(cat) = &app.active_node {
+                app.input_mode = InputMode::Confirming(ConfirmAction::DeleteCategoryPosts(cat.clone()));
             }
         }
-        KeyCode::Char('y') => app.copy_url_to_clipboard(),
-        KeyCode::Char('r') => {
-            if !app.is_loading {
-                app.is_loading = true;
-                let db_clone = db.clone();
-                let tx_clone = tx.clone();
-                let node = app.active_node.clone();
-                tokio::spawn(async move {
-                    fetch_feeds_for_node(db_clone, node, tx_clone).await;
-                });
-            }
+        Action::OpenBrowser => app.open_in_browser(),
+        Action::CopyUrl => app.copy_url_to_clipboard(),
+        Action::ExportJson => app.export_current_view(ExportFormat::Json),
+        Action::ExportMarkdown => app.export_current_view(ExportFormat::Markdown),
+        Action::ShareDigest => app.share_digest(),
+        Action::Refresh if !app.is_loading => {
+            app.is_loading = true;
+            let db_clone = db.clone();
+            let tx_clone = tx.clone();
+            let node = app.active_node.clone();
+            let config = app.config.clone();
+            tokio::spawn(async move {
+                fetch_feeds_for_node(db_clone, node, config, tx_clone).await;
+            });
         }
-        KeyCode::Char('+') => {
+        Action::AddFeed => {
             app.input_mode = InputMode::AddingFeed;
         }
         _ => {}
     }
 }
 
-fn handle_article_input(app: &mut App, key: KeyCode) {
-    match key {
-        KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('h') => app.close_article(),
-        KeyCode::Down | KeyCode::Char('j') => {
+fn handle_article_input(app: &mut App, action: Action, fulltext_tx: &tokio::sync::mpsc::Sender<FullText>) {
+    // Covers short articles that fit on screen and never scroll
+    app.mark_read_if_scrolled_to_end();
+
+    // Any manual input takes over from auto-scroll
+    if !matches!(action, Action::AutoScroll | Action::PauseAutoScroll) {
+        app.set_auto_scroll_paused(true);
+    }
+
+    match action {
+        Action::CloseArticle => app.close_article(),
+        Action::ScrollDown => {
             app.scroll_offset = app.scroll_offset.saturating_add(1);
+            app.mark_read_if_scrolled_to_end();
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        Action::ScrollUp => {
             app.scroll_offset = app.scroll_offset.saturating_sub(1);
         }
-        KeyCode::PageDown => {
+        // With wrapping off these scroll sideways; otherwise they change focus
+        Action::ScrollLeft if !app.article_wrap => {
+            app.h_scroll_offset = app.h_scroll_offset.saturating_sub(4);
+        }
+        Action::ScrollRight if !app.article_wrap => {
+            app.h_scroll_offset = app.h_scroll_offset.saturating_add(4);
+        }
+        Action::ScrollLeft => app.focus_left(),
+        Action::ScrollRight => app.focus_right(),
+        Action::NextArticle => app.open_next_article(),
+        Action::PreviousArticle => app.open_previous_article(),
+        Action::PageDown => {
             app.scroll_offset = app.scroll_offset.saturating_add(10);
+            app.mark_read_if_scrolled_to_end();
         }
-        KeyCode::PageUp => {
+        Action::PageUp => {
             app.scroll_offset = app.scroll_offset.saturating_sub(10);
         }
-        KeyCode::Char('b') => app.toggle_bookmark(),
-        KeyCode::Char('l') => app.toggle_read_later(),
-        KeyCode::Char('a') => app.toggle_archived(),
-        KeyCode::Char('o') => {
-            if let Some(post) = app.posts.get(app.selected_index) {
-                let _ = open::that(&post.url);
-                app.message = Some("Opened in browser".to_string());
+        Action::HalfPageDown => app.half_page_article(true),
+        Action::HalfPageUp => app.half_page_article(false),
+        Action::Top => app.scroll_offset = 0,
+        Action::Bottom => app.scroll_article_to_end(),
+        Action::ToggleBookmark => app.toggle_bookmark(),
+        Action::ToggleReadLater => app.toggle_read_later(),
+        Action::ToggleArchived => app.toggle_archived(),
+        Action::OpenBrowser => app.open_in_browser(),
+        Action::CopyUrl => app.copy_url_to_clipboard(),
+        Action::ToggleSummary => app.toggle_summary(),
+        Action::FullText => fetch_full_text(app, fulltext_tx, false),
+        Action::LivePage => {
+            let shown = app.toggle_live_page();
+            if !shown {
+                fetch_full_text(app, fulltext_tx, true);
             }
         }
-        KeyCode::Char('y') => app.copy_url_to_clipboard(),
+        Action::SaveLivePage => app.save_live_page(),
+        Action::PlayEnclosure => app.play_enclosure(),
+        Action::ToggleWrap => app.toggle_article_wrap(),
+        Action::AutoScroll => app.toggle_auto_scroll(),
+        Action::PauseAutoScroll => {
+            let paused = app.is_auto_scrolling();
+            app.set_auto_scroll_paused(paused);
+        }
         _ => {}
     }
 }
 
+/// Lines the wheel scrolls an article per notch
+const WHEEL_SCROLL_LINES: u16 = 3;
+
+fn handle_mouse_input(app: &mut App, mouse: MouseEvent) {
+    let in_article = app.focus == FocusPane::Article;
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) if !in_article => app.click(mouse.column, mouse.row),
+        MouseEventKind::ScrollDown if in_article => {
+            app.set_auto_scroll_paused(true);
+            app.scroll_offset = app.scroll_offset.saturating_add(WHEEL_SCROLL_LINES);
+            app.mark_read_if_scrolled_to_end();
+        }
+        MouseEventKind::ScrollUp if in_article => {
+            app.set_auto_scroll_paused(true);
+            app.scroll_offset = app.scroll_offset.saturating_sub(WHEEL_SCROLL_LINES);
+        }
+        MouseEventKind::ScrollDown if app.focus == FocusPane::Posts => app.next_post(),
+        MouseEventKind::ScrollUp if app.focus == FocusPane::Posts => app.previous_post(),
+        _ => {}
+    }
+}
+
+/// The stored name of the category `name` refers to, matched ignoring case
+/// so the CLI can take any capitalisation
+fn resolve_category(db: &db::Database, name: &str) -> Result<String, String> {
+    let _ = db.ensure_categories_table();
+    let categories = db.get_categories().map_err(|e| e.to_string())?;
+    categories
+        .iter()
+        .find(|c| c.eq_ignore_ascii_case(name.trim()))
+        .cloned()
+        .ok_or_else(|| format!("No category named '{}'. Categories: {}", name, categories.join(", ")))
+}
+
 async fn handle_command(command: Commands, cli: &Cli) -> Result<(), Box<dyn Error>> {
     match command {
         Commands::ResetDb { yes } => {
@@ -591,31 +1383,32 @@ async fn handle_command(command: Commands, cli: &Cli) -> Result<(), Box<dyn Erro
             println!("Database reset successfully.");
         }
 
-        Commands::ExportFeeds { output } => {
+        Commands::ExportFeeds { output, category } => {
             let db_path = cli.get_db_path();
             let db = db::Database::init_with_path(&db_path)?;
-            let feeds = db.get_feeds()?;
-
-            let mut opml = String::from(
-                r#"<?xml version="1.0" encoding="UTF-8"?>
-<opml version="2.0">
-  <head>
-    <title>News Feed Subscriptions</title>
-  </head>
-  <body>
-"#,
-            );
-
-            for feed in feeds {
-                let title = feed.title.as_deref().unwrap_or("Untitled");
-                opml.push_str(&format!(
-                    r#"    <outline type="rss" text="{}" xmlUrl="{}" category="{}"/>
-"#,
-                    title, feed.url, feed.category
-                ));
-            }
 
-            opml.push_str("  </body>\n</opml>\n");
+            let opml = match category {
+                Some(name) => {
+                    let category = resolve_category(&db, &name)?;
+                    let feeds = db.get_feeds_by_category(&category)?;
+                    if feeds.is_empty() {
+                        eprintln!("Category '{}' has no feeds; writing an empty folder.", category);
+                    }
+                    opml::to_opml(&[(category, feeds)])
+                }
+                None => {
+                    let _ = db.ensure_categories_table();
+                    let mut groups = Vec::new();
+                    for category in db.get_categories()? {
+                        let feeds = db.get_feeds_by_category(&category)?;
+                        // Empty categories have nothing to subscribe to
+                        if !feeds.is_empty() {
+                            groups.push((category, feeds));
+                        }
+                    }
+                    opml::to_opml(&groups)
+                }
+            };
 
             if let Some(output_path) = output {
                 std::fs::write(&output_path, opml)?;
@@ -625,41 +1418,185 @@ async fn handle_command(command: Commands, cli: &Cli) -> Result<(), Box<dyn Erro
             }
         }
 
-        Commands::ImportFeeds { input } => {
-            println!("Reading from: {}", input.display());
+        Commands::ExportJson { output } => {
+            let db = db::Database::init_with_path(cli.get_db_path())?;
+            let json = export::backup_to_json(&db)?;
 
-            let content = std::fs::read_to_string(&input)?;
+            if let Some(output_path) = output {
+                std::fs::write(&output_path, json)?;
+                println!("Backup written to: {}", output_path.display());
+            } else {
+                println!("{}", json);
+            }
+        }
+
+        Commands::ImportJson { input } => {
+            let db = db::Database::init_with_path(cli.get_db_path())?;
+            let json = std::fs::read_to_string(&input)?;
+            let (feeds, posts) = export::restore_backup(&db, &json)?;
+            println!("Restored {} feeds and {} posts from {}.", feeds, posts, input.display());
+        }
+
+        Commands::ImportFeeds { input } => {
+            let content = if input.starts_with("http://") || input.starts_with("https://") {
+                println!("Fetching: {}", input);
+                fetch_opml(&input).await?
+            } else {
+                println!("Reading from: {}", input);
+                std::fs::read_to_string(&input)?
+            };
             let db_path = cli.get_db_path();
             let db = db::Database::init_with_path(&db_path)?;
 
-            let mut count = 0;
-            for line in content.lines() {
-                if line.contains("xmlUrl=") {
-                    if let Some(start) = line.find("xmlUrl=\"") {
-                        let rest = &line[start + 8..];
-                        if let Some(end) = rest.find('"') {
-                            let url = &rest[..end];
-                            let category = if let Some(cat_start) = line.find("category=\"") {
-                                let cat_rest = &line[cat_start + 10..];
-                                if let Some(cat_end) = cat_rest.find('"') {
-                                    &cat_rest[..cat_end]
-                                } else {
-                                    "General"
-                                }
-                            } else {
-                                "General"
-                            };
-
-                            match db.add_feed_with_category(url, category) {
-                                Ok(_) => count += 1,
-                                Err(e) => eprintln!("Failed to add {}: {}", url, e),
-                            }
+            // Folderless OPML outlines go to Uncategorized like the TUI import,
+            // while a plain URL list, which never has folders, goes to General
+            let (feeds, fallback_category) = if opml::is_opml(&content) {
+                (opml::parse(&content)?, None)
+            } else {
+                (opml::parse_url_list(&content)?, Some("General"))
+            };
+
+            let mut imported = 0;
+            let mut skipped = 0;
+            for feed in feeds {
+                if let Ok(Some(_)) = db.find_feed_by_url(&feed.url) {
+                    skipped += 1;
+                    continue;
+                }
+                let result = match feed.category.as_deref().or(fallback_category) {
+                    Some(category) => db.add_feed_with_category(&feed.url, category),
+                    None => db.add_feed(&feed.url),
+                };
+                match result {
+                    Ok(id) => {
+                        if let Some(title) = &feed.title {
+                            let _ = db.set_feed_title_if_missing(id, title);
                         }
+                        imported += 1;
                     }
+                    Err(e) => eprintln!("Failed to add {}: {}", feed.url, e),
+                }
+            }
+
+            println!("Imported {} feeds from {} ({} already subscribed).", imported, input, skipped);
+        }
+
+        Commands::ExportMarkdown { dir, unread_only } => {
+            let db = db::Database::init_with_path(cli.get_db_path())?;
+            db.ensure_categories_table()?;
+            let written = export::export_reading_lists(&db, &dir, unread_only)?;
+
+            for (path, count) in &written {
+                println!("  {} ({} posts)", path.display(), count);
+            }
+            println!("Wrote {} reading lists to {}.", written.len(), dir.display());
+        }
+
+        Commands::Refresh { category } => {
+            let (config, unknown_keys) = config::load_config_from_path(cli.get_config_path())?;
+            if let Some(note) = unknown_keys {
+                eprintln!("{}", note);
+            }
+            let db_path = cli.get_db_path();
+            let db = db::Database::init_with_path(&db_path)?;
+            let _ = db.ensure_categories_table();
+
+            let feeds = match category {
+                Some(name) => db.get_feeds_by_category(&resolve_category(&db, &name)?)?,
+                None => db.get_feeds()?,
+            };
+            if feeds.is_empty() {
+                println!("No feeds to refresh.");
+                return Ok(());
+            }
+
+            println!("Refreshing {} feeds...", feeds.len());
+            let started = Instant::now();
+            let total = feeds.len();
+            let db = Arc::new(Mutex::new(db));
+            let (new_posts, failed) = fetch_feeds(&db, feeds, &config, |feed, result| {
+                let name = feed.title.as_deref().unwrap_or(&feed.url);
+                match result {
+                    Ok(0) => println!("  {}: no new posts", name),
+                    Ok(count) => println!("  {}: {} new", name, count),
+                    Err(e) => println!("  {}: failed, {}", name, e),
                 }
+            })
+            .await;
+            println!(
+                "Fetched {} feeds in {:.1}s: {} new posts, {} failed.",
+                total,
+                started.elapsed().as_secs_f32(),
+                new_posts,
+                failed
+            );
+        }
+
+        Commands::ListPosts { unread, category, limit } => {
+            let db_path = cli.get_db_path();
+            let db = db::Database::init_with_path(&db_path)?;
+
+            let category = match category {
+                Some(name) => Some(resolve_category(&db, &name)?),
+                None => None,
+            };
+            let posts = db.get_posts(db::PostFilter {
+                only_unread: unread,
+                only_bookmarked: false,
+                only_archived: false,
+                only_read_later: false,
+                category,
+                limit,
+                offset: 0,
+            })?;
+
+            // One post per line with tabs between fields, so tabs and newlines inside them become spaces
+            let field = |text: &str| text.replace(['\t', '\n', '\r'], " ");
+            for post in posts {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    post.id,
+                    if post.is_read { "read" } else { "unread" },
+                    field(post.feed_title.as_deref().unwrap_or_default()),
+                    field(&post.title)
+                );
+            }
+        }
+
+        Commands::Open { id } => {
+            let db_path = cli.get_db_path();
+            let db = db::Database::init_with_path(&db_path)?;
+
+            let Some(post) = db.get_post_by_id(id)? else {
+                return Err(format!("No post with id {}", id).into());
+            };
+            let url = post.url.trim();
+            if url.is_empty() || reqwest::Url::parse(url).is_err() {
+                return Err(format!("Post {} has no valid link", id).into());
             }
+            open::that(url)?;
+            db.mark_as_read(post.id)?;
+            println!("Opened: {}", post.title);
+        }
+
+        Commands::MarkRead { feed, all } => {
+            let db_path = cli.get_db_path();
+            let db = db::Database::init_with_path(&db_path)?;
 
-            println!("Imported {} feeds.", count);
+            let (count, target) = match feed {
+                None if all => (db.mark_all_read()?, "all feeds".to_string()),
+                None => return Err("Give a feed URL or category, or --all".into()),
+                Some(name) => {
+                    if let Some(feed) = db.find_feed_by_url(name.trim())? {
+                        (db.mark_feed_read(feed.id)?, feed.title.unwrap_or(feed.url))
+                    } else {
+                        let category = resolve_category(&db, &name)
+                            .map_err(|e| format!("No feed has that URL. {}", e))?;
+                        (db.mark_category_read(&category)?, category)
+                    }
+                }
+            };
+            println!("Marked {} posts read in {}.", count, target);
         }
 
         Commands::Cleanup { days, yes } => {
@@ -706,11 +1643,48 @@ async fn handle_command(command: Commands, cli: &Cli) -> Result<(), Box<dyn Erro
                 println!("Statistics:");
                 println!("  Total feeds: {}", total_feeds);
                 println!("  Total posts: {}", total_posts);
+
+                let uncategorized = db.get_uncategorized_feeds_count()?;
+                if uncategorized > 0 {
+                    println!(
+                        "  Uncategorized feeds: {} (press 'm' on Uncategorized in the sidebar to sort them)",
+                        uncategorized
+                    );
+                }
             } else {
                 println!("Database does not exist yet. Run 'news' to create it.");
             }
         }
 
+        Commands::OpenDirs => {
+            let config_path = cli.get_config_path();
+            let db_path = cli.get_db_path();
+            let dirs = [config_path.parent(), db_path.parent()];
+
+            for (i, dir) in dirs.iter().enumerate() {
+                let Some(dir) = dir else { continue };
+                if dirs[..i].contains(&Some(*dir)) {
+                    continue;
+                }
+                println!("{}", dir.display());
+                if let Err(e) = open::that(dir) {
+                    eprintln!("  Could not open: {}", e);
+                }
+            }
+        }
+
+        Commands::Doctor => {
+            let config_path = cli.get_config_path();
+            let db_path = cli.get_db_path();
+            let db = if db_path.exists() {
+                Some(db::Database::init_with_path(&db_path)?)
+            } else {
+                None
+            };
+
+            println!("{}", diagnostics::collect(&config_path, &db_path, db.as_ref()));
+        }
+
         Commands::ListFeeds => {
             let db_path = cli.get_db_path();
 
@@ -738,3 +1712,74 @@ async fn handle_command(command: Commands, cli: &Cli) -> Result<(), Box<dyn Erro
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    /// A database with one old post, one old starred post and one fresh post
+    fn db_with_posts() -> db::Database {
+        let db = db::Database::init_with_path(":memory:").unwrap();
+        let feed = db.add_feed("https://example.com/feed").unwrap();
+        let old = Some(Utc::now() - Duration::days(60));
+        db.insert_post(feed, "Old", "https://example.com/old", None, None, old).unwrap();
+        db.insert_post(feed, "Old starred", "https://example.com/starred", None, None, old).unwrap();
+        db.insert_post(feed, "Fresh", "https://example.com/fresh", None, None, Some(Utc::now())).unwrap();
+        let starred = db
+            .get_posts_by_feed(feed)
+            .unwrap()
+            .into_iter()
+            .find(|p| p.title == "Old starred")
+            .unwrap();
+        db.toggle_bookmark(starred.id).unwrap();
+        db
+    }
+
+    fn titles(db: &db::Database) -> Vec<String> {
+        let feed = db.find_feed_by_url("https://example.com/feed").unwrap().unwrap();
+        let mut titles: Vec<_> = db.get_posts_by_feed(feed.id).unwrap().into_iter().map(|p| p.title).collect();
+        titles.sort();
+        titles
+    }
+
+    #[test]
+    fn startup_cleanup_removes_old_unstarred_posts() {
+        let db = db_with_posts();
+        let app_config = config::AppConfig {
+            startup_cleanup: true,
+            cleanup_days: 30,
+            ..Default::default()
+        };
+
+        let note = startup_cleanup(&db, &app_config);
+
+        assert_eq!(note.as_deref(), Some("Cleaned up 1 posts older than 30 days"));
+        assert_eq!(titles(&db), vec!["Fresh", "Old starred"]);
+    }
+
+    #[test]
+    fn startup_cleanup_does_nothing_when_off() {
+        let db = db_with_posts();
+        let app_config = config::AppConfig {
+            startup_cleanup: false,
+            cleanup_days: 30,
+            ..Default::default()
+        };
+
+        assert_eq!(startup_cleanup(&db, &app_config), None);
+        assert_eq!(titles(&db).len(), 3);
+    }
+
+    #[test]
+    fn resolve_category_ignores_case_and_lists_names_on_a_miss() {
+        let db = db::Database::init_with_path(":memory:").unwrap();
+        db.ensure_categories_table().unwrap();
+        db.add_category("Tech").unwrap();
+
+        assert_eq!(resolve_category(&db, " tech ").unwrap(), "Tech");
+        let error = resolve_category(&db, "Cooking").unwrap_err();
+        assert!(error.starts_with("No category named 'Cooking'. Categories: "), "{}", error);
+        assert!(error.contains("Tech"), "{}", error);
+    }
+}
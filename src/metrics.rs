@@ -0,0 +1,17 @@
+use std::time::Duration;
+
+/// Timings collected for the `--verbose` debug overlay. Only allocated when
+/// verbose mode is on, so the normal path never pays for it.
+#[derive(Debug, Default)]
+pub struct DebugMetrics {
+    pub visible: bool,
+    pub frame_time: Duration,
+    pub last_fetch: Option<Duration>,
+    pub last_query: Option<Duration>,
+}
+
+impl DebugMetrics {
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+}
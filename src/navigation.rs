@@ -1,10 +1,12 @@
-use crate::db::Database;
+use crate::db::{Database, UNCATEGORIZED};
 use std::collections::HashMap;
 use std::time::Instant;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SmartView {
     Fresh,
+    /// Newest posts from every feed, read or not, capped per feed
+    AllFeeds,
     Starred,
     ReadLater,
     Archived,
@@ -14,6 +16,7 @@ impl SmartView {
     pub fn title(&self) -> &'static str {
         match self {
             SmartView::Fresh => "Fresh",
+            SmartView::AllFeeds => "All Feeds",
             SmartView::Starred => "Starred",
             SmartView::ReadLater => "Read Later",
             SmartView::Archived => "Archived",
@@ -23,15 +26,34 @@ impl SmartView {
     pub fn icon(&self) -> &'static str {
         match self {
             SmartView::Fresh => "󰈸",
+            SmartView::AllFeeds => "󰑫",
             SmartView::Starred => "★",
             SmartView::ReadLater => "󰃰",
             SmartView::Archived => "󰆧",
         }
     }
 
+    /// Stable name used when the view is saved as a preference
+    pub fn key(&self) -> &'static str {
+        match self {
+            SmartView::Fresh => "fresh",
+            SmartView::AllFeeds => "all_feeds",
+            SmartView::Starred => "starred",
+            SmartView::ReadLater => "read_later",
+            SmartView::Archived => "archived",
+        }
+    }
+
+    /// Inverse of `key`, also accepting `-` for `_` as typed in `config.toml`
+    pub fn from_key(key: &str) -> Option<SmartView> {
+        let key = key.trim().to_lowercase().replace('-', "_");
+        SmartView::all().into_iter().find(|sv| sv.key() == key)
+    }
+
     pub fn all() -> Vec<SmartView> {
         vec![
             SmartView::Fresh,
+            SmartView::AllFeeds,
             SmartView::Starred,
             SmartView::ReadLater,
             SmartView::Archived,
@@ -60,6 +82,22 @@ impl NavNode {
             NavNode::Category(_) => "󰉋",
         }
     }
+
+    /// `view:<key>` or `category:<name>`, as stored in `user_preferences`
+    pub fn to_preference(&self) -> String {
+        match self {
+            NavNode::SmartView(sv) => format!("view:{}", sv.key()),
+            NavNode::Category(name) => format!("category:{}", name),
+        }
+    }
+
+    pub fn from_preference(value: &str) -> Option<NavNode> {
+        if let Some(key) = value.strip_prefix("view:") {
+            SmartView::from_key(key).map(NavNode::SmartView)
+        } else {
+            value.strip_prefix("category:").map(|name| NavNode::Category(name.to_string()))
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -83,6 +121,8 @@ pub struct SidebarState {
     pub category_index: usize,
     pub counts: HashMap<NavNode, usize>,
     pub last_fetched: HashMap<NavNode, Instant>,
+    /// Per-feed cap of the All Feeds view, so its count matches what it shows
+    pub all_feeds_per_feed: usize,
 }
 
 impl SidebarState {
@@ -95,6 +135,7 @@ impl SidebarState {
             category_index: 0,
             counts: HashMap::new(),
             last_fetched: HashMap::new(),
+            all_feeds_per_feed: 0,
         }
     }
 
@@ -103,6 +144,11 @@ impl SidebarState {
         if self.categories.is_empty() {
             self.categories.push("General".to_string());
         }
+        // Keep the Uncategorized bucket at the bottom so it reads as "needs sorting"
+        if let Some(pos) = self.categories.iter().position(|c| c == UNCATEGORIZED) {
+            let uncategorized = self.categories.remove(pos);
+            self.categories.push(uncategorized);
+        }
     }
 
     pub fn update_counts(&mut self, db: &Database) {
@@ -110,6 +156,10 @@ impl SidebarState {
             NavNode::SmartView(SmartView::Fresh),
             db.get_count("SELECT COUNT(*) FROM posts WHERE is_read = 0").unwrap_or(0),
         );
+        self.counts.insert(
+            NavNode::SmartView(SmartView::AllFeeds),
+            db.count_all_feeds_posts(self.all_feeds_per_feed).unwrap_or(0),
+        );
         self.counts.insert(
             NavNode::SmartView(SmartView::Starred),
             db.get_count("SELECT COUNT(*) FROM posts WHERE is_bookmarked = 1").unwrap_or(0),
@@ -123,12 +173,9 @@ impl SidebarState {
             db.get_count("SELECT COUNT(*) FROM posts WHERE is_archived = 1").unwrap_or(0),
         );
 
+        // Categories show what's left to read, like Fresh
         for cat in &self.categories {
-            let count = db.get_count(&format!(
-                "SELECT COUNT(*) FROM posts p JOIN feeds f ON p.feed_id = f.id WHERE f.category = '{}'",
-                cat.replace("'", "''")
-            )).unwrap_or(0);
-            self.counts.insert(NavNode::Category(cat.clone()), count);
+            self.counts.insert(NavNode::Category(cat.clone()), db.get_category_post_count(cat));
         }
     }
 
@@ -136,6 +183,29 @@ impl SidebarState {
         *self.counts.get(node).unwrap_or(&0)
     }
 
+    /// Move the selection onto `node`. Returns false, leaving the selection
+    /// alone, when it isn't in the sidebar (a category deleted since).
+    pub fn select_node(&mut self, node: &NavNode) -> bool {
+        match node {
+            NavNode::SmartView(view) => match self.smart_views.iter().position(|v| v == view) {
+                Some(index) => {
+                    self.section = SidebarSection::SmartViews;
+                    self.smart_view_index = index;
+                    true
+                }
+                None => false,
+            },
+            NavNode::Category(name) => match self.categories.iter().position(|c| c == name) {
+                Some(index) => {
+                    self.section = SidebarSection::Categories;
+                    self.category_index = index;
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+
     pub fn selected_node(&self) -> NavNode {
         match self.section {
             SidebarSection::SmartViews => {
@@ -151,6 +221,26 @@ impl SidebarState {
         }
     }
 
+    /// Select the item drawn on `row` of the sidebar, counting the "VIEWS"
+    /// heading as row 0 and the blank line plus "CATEGORIES" heading after the
+    /// views. Returns false for headings and empty space.
+    pub fn select_row(&mut self, row: usize) -> bool {
+        let views = self.smart_views.len();
+        if (1..=views).contains(&row) {
+            self.section = SidebarSection::SmartViews;
+            self.smart_view_index = row - 1;
+            return true;
+        }
+        match row.checked_sub(views + 3) {
+            Some(index) if index < self.categories.len() => {
+                self.section = SidebarSection::Categories;
+                self.category_index = index;
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub fn next(&mut self) {
         match self.section {
             SidebarSection::SmartViews => {
@@ -0,0 +1,285 @@
+use crate::db::Feed;
+use quick_xml::encoding::Decoder;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::error::Error;
+
+/// Make text safe inside a double-quoted XML attribute: `&`, `<`, `>`, `"`
+/// and `'` become entities, and control characters XML 1.0 doesn't allow at
+/// all (they turn up in scraped feed titles) are dropped.
+fn xml_escape(value: &str) -> String {
+    let allowed: String = value
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    quick_xml::escape::escape(allowed.as_str()).into_owned()
+}
+
+/// A feed subscription read from an OPML document
+#[derive(Debug, Clone)]
+pub struct OpmlFeed {
+    pub url: String,
+    /// Innermost enclosing folder outline, or the feed's own `category` attribute
+    pub category: Option<String>,
+    pub title: Option<String>,
+}
+
+/// Write feeds as an OPML document, one folder outline per category with
+/// its feeds nested inside, as Feedly and Inoreader export them. Each feed
+/// also keeps a `category` attribute for readers that only look at that.
+pub fn to_opml(groups: &[(String, Vec<Feed>)]) -> String {
+    let mut opml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <head>
+    <title>News Feed Subscriptions</title>
+  </head>
+  <body>
+"#,
+    );
+
+    for (category, feeds) in groups {
+        opml.push_str(&format!("    <outline text=\"{0}\" title=\"{0}\">\n", xml_escape(category)));
+        for feed in feeds {
+            let title = feed.title.as_deref().unwrap_or("Untitled");
+            opml.push_str(&format!(
+                "      <outline type=\"rss\" text=\"{}\" xmlUrl=\"{}\" category=\"{}\"/>\n",
+                xml_escape(title),
+                xml_escape(&feed.url),
+                xml_escape(&feed.category)
+            ));
+        }
+        opml.push_str("    </outline>\n");
+    }
+
+    opml.push_str("  </body>\n</opml>\n");
+    opml
+}
+
+/// Parse the feed outlines (`xmlUrl`) out of an OPML document.
+///
+/// Folder outlines can nest to any depth; a feed takes the name of the
+/// innermost folder it sits in. Outlines with only an `htmlUrl` point at a
+/// website rather than a feed and are skipped.
+pub fn parse(content: &str) -> Result<Vec<OpmlFeed>, Box<dyn Error>> {
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+
+    let mut feeds = Vec::new();
+    // One entry per open <outline>: the folder name when it's a folder
+    let mut folders: Vec<Option<String>> = Vec::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) if e.local_name().as_ref() == b"outline" => {
+                let outline = Outline::from_element(&e, reader.decoder())?;
+                let folder = match outline.xml_url {
+                    Some(_) => {
+                        feeds.push(outline.into_feed(&folders));
+                        None
+                    }
+                    None => outline.text,
+                };
+                folders.push(folder);
+            }
+            Event::Empty(e) if e.local_name().as_ref() == b"outline" => {
+                let outline = Outline::from_element(&e, reader.decoder())?;
+                if outline.xml_url.is_some() {
+                    feeds.push(outline.into_feed(&folders));
+                }
+            }
+            Event::End(e) if e.local_name().as_ref() == b"outline" => {
+                folders.pop();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(feeds)
+}
+
+/// Whether `content` looks like an XML (OPML) document rather than a plain
+/// list of URLs
+pub fn is_opml(content: &str) -> bool {
+    content.trim_start_matches('\u{feff}').trim_start().starts_with('<')
+}
+
+/// Parse a plain subscription list such as a `feeds.txt`: one feed URL per
+/// line, with blank lines and `#` comment lines ignored. The feeds carry no
+/// category; the caller picks one.
+pub fn parse_url_list(content: &str) -> Result<Vec<OpmlFeed>, Box<dyn Error>> {
+    let mut feeds = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim().trim_start_matches('\u{feff}');
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with("http://") && !line.starts_with("https://") {
+            return Err(format!("line {} isn't a feed URL: {}", number + 1, line).into());
+        }
+        feeds.push(OpmlFeed {
+            url: line.to_string(),
+            category: None,
+            title: None,
+        });
+    }
+    Ok(feeds)
+}
+
+struct Outline {
+    xml_url: Option<String>,
+    text: Option<String>,
+    title: Option<String>,
+    category: Option<String>,
+}
+
+impl Outline {
+    fn from_element(element: &BytesStart, decoder: Decoder) -> Result<Self, Box<dyn Error>> {
+        let mut outline = Outline {
+            xml_url: None,
+            text: None,
+            title: None,
+            category: None,
+        };
+
+        for attr in element.attributes() {
+            let attr = attr?;
+            let value = attr.decode_and_unescape_value(decoder)?.trim().to_string();
+            if value.is_empty() {
+                continue;
+            }
+            // Exporters disagree on casing (xmlUrl vs xmlurl)
+            let key = String::from_utf8_lossy(attr.key.local_name().as_ref()).to_lowercase();
+            match key.as_str() {
+                "xmlurl" => outline.xml_url = Some(value),
+                "text" => outline.text = Some(value),
+                "title" => outline.title = Some(value),
+                "category" => outline.category = Some(value),
+                _ => {}
+            }
+        }
+
+        // Some exporters only set `title` on folders
+        if outline.text.is_none() {
+            outline.text = outline.title.clone();
+        }
+        Ok(outline)
+    }
+
+    fn into_feed(self, folders: &[Option<String>]) -> OpmlFeed {
+        let folder = folders.iter().rev().find_map(|f| f.clone());
+        // OPML 2.0 categories are slash-delimited paths like "/Tech/Rust"
+        let attribute_category = self.category.and_then(|c| {
+            c.split(',')
+                .next()
+                .and_then(|path| path.trim().trim_matches('/').rsplit('/').next().map(str::to_string))
+                .filter(|name| !name.is_empty())
+        });
+
+        OpmlFeed {
+            url: self.xml_url.unwrap_or_default(),
+            category: folder.or(attribute_category),
+            title: self.text.or(self.title),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEEDLY: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="1.0">
+  <head>
+    <title>Alex subscriptions in feedly Cloud</title>
+  </head>
+  <body>
+    <outline text="Tech" title="Tech">
+      <outline type="rss" text="Hacker News" title="Hacker News" xmlUrl="https://news.ycombinator.com/rss" htmlUrl="https://news.ycombinator.com/"/>
+      <outline type="rss" text="Ars Technica &amp; Friends" title="Ars Technica &amp; Friends" xmlUrl="https://feeds.arstechnica.com/arstechnica/index" htmlUrl="https://arstechnica.com"/>
+    </outline>
+    <outline text="Cooking" title="Cooking">
+      <outline type="rss" text="Serious Eats" title="Serious Eats" xmlUrl="https://www.seriouseats.com/feeds/all" htmlUrl="https://www.seriouseats.com"/>
+    </outline>
+  </body>
+</opml>
+"#;
+
+    const INOREADER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="1.0">
+    <head>
+        <title>Subscriptions from Inoreader [https://www.inoreader.com]</title>
+    </head>
+    <body>
+        <outline title="Programming" text="Programming">
+            <outline title="Languages" text="Languages">
+                <outline text="This Week in Rust" title="This Week in Rust" type="rss" xmlUrl="https://this-week-in-rust.org/rss.xml" htmlUrl="https://this-week-in-rust.org/"/>
+            </outline>
+            <outline text='Go Blog' type='rss' xmlurl='https://go.dev/blog/feed.atom'/>
+        </outline>
+        <outline text="Uncategorised" type="rss" xmlUrl="https://example.com/feed.xml" category="/News/World"/>
+        <outline text="Just a website" htmlUrl="https://example.com/"/>
+    </body>
+</opml>
+"#;
+
+    #[test]
+    fn parses_feedly_export() {
+        let feeds = parse(FEEDLY).unwrap();
+        let got: Vec<_> = feeds
+            .iter()
+            .map(|f| (f.url.as_str(), f.category.as_deref(), f.title.as_deref()))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("https://news.ycombinator.com/rss", Some("Tech"), Some("Hacker News")),
+                (
+                    "https://feeds.arstechnica.com/arstechnica/index",
+                    Some("Tech"),
+                    Some("Ars Technica & Friends")
+                ),
+                ("https://www.seriouseats.com/feeds/all", Some("Cooking"), Some("Serious Eats")),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_inoreader_export() {
+        let feeds = parse(INOREADER).unwrap();
+        let got: Vec<_> = feeds
+            .iter()
+            .map(|f| (f.url.as_str(), f.category.as_deref(), f.title.as_deref()))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("https://this-week-in-rust.org/rss.xml", Some("Languages"), Some("This Week in Rust")),
+                ("https://go.dev/blog/feed.atom", Some("Programming"), Some("Go Blog")),
+                ("https://example.com/feed.xml", Some("World"), Some("Uncategorised")),
+            ]
+        );
+    }
+
+    #[test]
+    fn exported_ampersand_title_round_trips() {
+        let feed = Feed {
+            id: 1,
+            url: "https://example.com/feed?a=1&b=2".to_string(),
+            title: Some("Tom & Jerry's <Weekly> \"News\"".to_string()),
+            category: "R&D".to_string(),
+            last_fetched_at: None,
+            last_error: None,
+            consecutive_failures: 0,
+            auto_category: false,
+            description: None,
+        };
+        let opml = to_opml(&[("R&D".to_string(), vec![feed.clone()])]);
+        let feeds = parse(&opml).unwrap();
+        assert_eq!(feeds.len(), 1);
+        assert_eq!(feeds[0].url, feed.url);
+        assert_eq!(feeds[0].title, feed.title);
+        assert_eq!(feeds[0].category.as_deref(), Some("R&D"));
+    }
+}
@@ -0,0 +1,54 @@
+/// Average adult silent-reading speed used for estimates
+const WORDS_PER_MINUTE: usize = 220;
+
+/// Estimated minutes to read an HTML (or plain text) body, rounded up.
+/// `None` when there's no text at all, so callers can show nothing.
+pub fn reading_minutes(html: &str) -> Option<u32> {
+    let words = word_count(html);
+    if words == 0 {
+        return None;
+    }
+    Some(words.div_ceil(WORDS_PER_MINUTE) as u32)
+}
+
+/// Words of text in an HTML (or plain text) body
+pub fn word_count(html: &str) -> usize {
+    count_words(&strip_tags(html))
+}
+
+/// Drop markup, plus the bodies of `<script>` and `<style>`. Cheap enough to
+/// run for every post on load, unlike a full html2text render.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        text.push(' ');
+        let after = &rest[start..];
+        let Some(end) = after.find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = after[1..end].trim_start().to_ascii_lowercase();
+        rest = &after[end + 1..];
+
+        for skipped in ["script", "style"] {
+            if tag.starts_with(skipped) {
+                let close = format!("</{}", skipped);
+                rest = match rest.to_ascii_lowercase().find(&close) {
+                    Some(pos) => &rest[pos..],
+                    None => "",
+                };
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+fn count_words(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .count()
+}
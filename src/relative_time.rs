@@ -0,0 +1,99 @@
+use chrono::{DateTime, Datelike, Utc};
+
+/// Compact "time ago" label such as "5m ago", "3d ago" or "2w ago"
+pub fn format_relative(dt: DateTime<Utc>) -> String {
+    format_relative_from(dt, Utc::now())
+}
+
+pub fn format_relative_from(dt: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - dt).num_seconds();
+    if secs < 60 {
+        return "just now".to_string();
+    }
+
+    let mins = secs / 60;
+    let hours = mins / 60;
+    let days = hours / 24;
+
+    if mins < 60 {
+        format!("{}m ago", mins)
+    } else if hours < 24 {
+        format!("{}h ago", hours)
+    } else if days < 7 {
+        format!("{}d ago", days)
+    } else if days < 30 {
+        format!("{}w ago", days / 7)
+    } else if days < 365 {
+        format!("{}mo ago", days / 30)
+    } else {
+        format!("{}y ago", days / 365)
+    }
+}
+
+/// Post date label: relative within the last week, then an absolute date
+/// (with the year only when it isn't the current one)
+pub fn format_post_date(dt: DateTime<Utc>) -> String {
+    format_post_date_from(dt, Utc::now())
+}
+
+pub fn format_post_date_from(dt: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    if (now - dt).num_days() < 7 {
+        return format_relative_from(dt, now);
+    }
+    let local = dt.with_timezone(&chrono::Local);
+    if local.year() == now.with_timezone(&chrono::Local).year() {
+        local.format("%b %d").to_string()
+    } else {
+        local.format("%b %d %Y").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn ago(duration: Duration) -> String {
+        let now = Utc.with_ymd_and_hms(2026, 6, 15, 12, 0, 0).unwrap();
+        format_relative_from(now - duration, now)
+    }
+
+    #[test]
+    fn just_now_under_a_minute() {
+        assert_eq!(ago(Duration::zero()), "just now");
+        assert_eq!(ago(Duration::seconds(59)), "just now");
+        // Clock skew can put a post slightly in the future
+        assert_eq!(ago(Duration::seconds(-30)), "just now");
+    }
+
+    #[test]
+    fn minutes_and_hours() {
+        assert_eq!(ago(Duration::seconds(60)), "1m ago");
+        assert_eq!(ago(Duration::minutes(59)), "59m ago");
+        assert_eq!(ago(Duration::minutes(60)), "1h ago");
+        assert_eq!(ago(Duration::hours(23) + Duration::minutes(59)), "23h ago");
+    }
+
+    #[test]
+    fn days_and_weeks() {
+        assert_eq!(ago(Duration::hours(24)), "1d ago");
+        assert_eq!(ago(Duration::days(6)), "6d ago");
+        assert_eq!(ago(Duration::days(7)), "1w ago");
+        assert_eq!(ago(Duration::days(29)), "4w ago");
+    }
+
+    #[test]
+    fn months_and_years() {
+        assert_eq!(ago(Duration::days(30)), "1mo ago");
+        assert_eq!(ago(Duration::days(364)), "12mo ago");
+        assert_eq!(ago(Duration::days(365)), "1y ago");
+        assert_eq!(ago(Duration::days(800)), "2y ago");
+    }
+
+    #[test]
+    fn post_date_switches_to_absolute_after_a_week() {
+        let now = Utc.with_ymd_and_hms(2026, 6, 15, 12, 0, 0).unwrap();
+        assert_eq!(format_post_date_from(now - Duration::days(6), now), "6d ago");
+        assert!(!format_post_date_from(now - Duration::days(7), now).ends_with("ago"));
+    }
+}
@@ -1,10 +1,424 @@
 use reqwest::Client;
 use feed_rs::parser;
+use scraper::{Html, Selector};
 use std::error::Error;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-pub async fn fetch_feed(client: &Client, url: &str) -> Result<feed_rs::model::Feed, Box<dyn Error + Send + Sync>> {
-    let resp = client.get(url).send().await?;
+/// Why a feed couldn't be fetched. Network trouble is usually temporary and
+/// worth retrying; a response that isn't a feed won't fix itself.
+#[derive(Debug)]
+pub enum FetchError {
+    /// The request failed or the body couldn't be read
+    Network(reqwest::Error),
+    /// The server answered with an error status
+    Status(reqwest::StatusCode),
+    /// The response arrived but isn't an RSS, Atom or JSON feed
+    NotAFeed(String),
+}
+
+impl FetchError {
+    /// Timeouts and dropped connections, which a second attempt may get past
+    pub fn is_transient(&self) -> bool {
+        match self {
+            FetchError::Network(e) => e.is_timeout() || e.is_connect() || e.is_body(),
+            FetchError::Status(status) => status.is_server_error() || *status == reqwest::StatusCode::TOO_MANY_REQUESTS,
+            FetchError::NotAFeed(_) => false,
+        }
+    }
+}
+
+/// Wait before the first retry of a transient failure; doubled for each one after
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Delay before retry `attempt` (counting from 0): exponential backoff plus up
+/// to half again of random jitter, so feeds on one host don't retry in step
+pub fn retry_delay(attempt: u32) -> Duration {
+    let backoff = RETRY_BASE_DELAY * 2u32.saturating_pow(attempt.min(6));
+    // Sub-second clock noise is plenty of randomness for spreading retries out
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.subsec_nanos());
+    backoff + backoff.mul_f64(f64::from(nanos % 1000) / 2000.0)
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Network(e) => write!(f, "{}", e),
+            FetchError::Status(status) => write!(f, "server returned {}", status),
+            FetchError::NotAFeed(reason) => write!(f, "not a valid feed ({})", reason),
+        }
+    }
+}
+
+impl Error for FetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchError::Network(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for FetchError {
+    fn from(e: reqwest::Error) -> Self {
+        FetchError::Network(e)
+    }
+}
+
+/// Parse a feed body, using the Content-Type to tell JSON Feed from XML and
+/// to recognise web pages served where a feed was expected
+fn parse_feed(content_type: Option<&str>, content: &[u8]) -> Result<feed_rs::model::Feed, FetchError> {
+    let content_type = content_type.unwrap_or_default().to_ascii_lowercase();
+    if content_type.contains("html") && looks_like_html(&String::from_utf8_lossy(content)) {
+        return Err(FetchError::NotAFeed("the server sent a web page".to_string()));
+    }
+
+    // JSON Feed is detected by its first character, so a byte-order mark hides it
+    let content = if content_type.contains("json") {
+        content.strip_prefix(b"\xEF\xBB\xBF".as_slice()).unwrap_or(content).trim_ascii_start()
+    } else {
+        content
+    };
+    parser::parse(content).map_err(|e| FetchError::NotAFeed(e.to_string()))
+}
+
+fn content_type(resp: &reqwest::Response) -> Option<String> {
+    resp.headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Send a request built by `request`, and when the server answers 403 send it
+/// once more with `fallback_user_agent`: hosts behind bot protection often
+/// turn away anything that doesn't look like a browser.
+async fn send_with_fallback(
+    request: impl Fn() -> reqwest::RequestBuilder,
+    fallback_user_agent: Option<&str>,
+) -> reqwest::Result<reqwest::Response> {
+    let resp = request().send().await?;
+    match fallback_user_agent {
+        Some(user_agent) if resp.status() == reqwest::StatusCode::FORBIDDEN => {
+            request().header(reqwest::header::USER_AGENT, user_agent).send().await
+        }
+        _ => Ok(resp),
+    }
+}
+
+/// Validators from the last successful fetch, replayed for a conditional GET
+#[derive(Debug, Clone, Default)]
+pub struct CacheHeaders {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+pub enum FetchOutcome {
+    /// Server answered 304; nothing to parse
+    NotModified,
+    Fetched {
+        feed: Box<feed_rs::model::Feed>,
+        cache: CacheHeaders,
+    },
+}
+
+/// Fetch a feed with `If-None-Match` / `If-Modified-Since` so unchanged feeds
+/// cost a 304 instead of a full download and parse.
+pub async fn fetch_feed_if_changed(
+    client: &Client,
+    url: &str,
+    cache: &CacheHeaders,
+    token: Option<&str>,
+    fallback_user_agent: Option<&str>,
+) -> Result<FetchOutcome, FetchError> {
+    let request = || {
+        let mut request = client.get(url);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(etag) = &cache.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &cache.last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+        request
+    };
+
+    let resp = send_with_fallback(request, fallback_user_agent).await?;
+    if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(FetchOutcome::NotModified);
+    }
+    if !resp.status().is_success() {
+        return Err(FetchError::Status(resp.status()));
+    }
+
+    let header = |name| {
+        resp.headers()
+            .get(name)
+            .and_then(|v: &reqwest::header::HeaderValue| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let cache = CacheHeaders {
+        etag: header(reqwest::header::ETAG),
+        last_modified: header(reqwest::header::LAST_MODIFIED),
+    };
+
+    let content_type = content_type(&resp);
     let content = resp.bytes().await?;
-    let feed = parser::parse(&content[..])?;
-    Ok(feed)
+    let feed = parse_feed(content_type.as_deref(), &content)?;
+    Ok(FetchOutcome::Fetched { feed: Box::new(feed), cache })
+}
+
+/// URL of the first audio or video attachment among an entry's media (RSS
+/// `<enclosure>` or `media:content`), such as a podcast episode
+pub fn enclosure_url(media: &[feed_rs::model::MediaObject]) -> Option<String> {
+    media
+        .iter()
+        .flat_map(|object| &object.content)
+        .find(|content| {
+            content
+                .content_type
+                .as_ref()
+                .is_some_and(|t| {
+                    let t = t.to_string();
+                    t.starts_with("audio/") || t.starts_with("video/")
+                })
+        })
+        .and_then(|content| content.url.as_ref())
+        .map(|url| url.to_string())
+}
+
+/// Hostname of a feed URL without a leading `www.`, used as a stand-in title
+pub fn feed_host(url: &str) -> Option<String> {
+    reqwest::Url::parse(url.trim())
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.trim_start_matches("www.").to_string()))
+}
+
+/// What a URL pasted by the user turned out to be
+pub enum Probe {
+    /// A parseable feed, with its title if it has one
+    Feed { title: Option<String> },
+    /// An HTML page rather than a feed; see [`discover_feeds`]
+    Page,
+}
+
+/// Fetch `url` and check that it's a feed. An HTML page is reported as such so
+/// the caller can look for its feed; anything else is an error.
+pub async fn probe_feed(
+    client: &Client,
+    url: &str,
+    token: Option<&str>,
+    fallback_user_agent: Option<&str>,
+) -> Result<Probe, FetchError> {
+    let request = || {
+        let request = client.get(url);
+        match token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    };
+    let resp = send_with_fallback(request, fallback_user_agent).await?;
+    if !resp.status().is_success() {
+        return Err(FetchError::Status(resp.status()));
+    }
+    let content_type = content_type(&resp);
+    let content = resp.bytes().await?;
+
+    match parse_feed(content_type.as_deref(), &content) {
+        Ok(feed) => Ok(Probe::Feed {
+            title: feed.title.map(|t| t.content.trim().to_string()).filter(|t| !t.is_empty()),
+        }),
+        Err(_) if looks_like_html(&String::from_utf8_lossy(&content)) => Ok(Probe::Page),
+        Err(e) => Err(e),
+    }
+}
+
+/// Paths many sites serve their feed from without advertising it
+const COMMON_FEED_PATHS: &[&str] = &["/feed", "/rss.xml", "/atom.xml"];
+
+/// Candidate feed URLs for a website: the feeds its HTML advertises, then the
+/// common feed paths on the same host. Candidates are unchecked; probe each
+/// before subscribing.
+pub async fn discover_feeds(client: &Client, url: &str, fallback_user_agent: Option<&str>) -> Vec<String> {
+    let Ok(resp) = send_with_fallback(|| client.get(url), fallback_user_agent).await else {
+        return Vec::new();
+    };
+    let base = resp.url().clone();
+    let html = resp.text().await.unwrap_or_default();
+
+    let mut candidates = feed_links(&html, &base);
+    for path in COMMON_FEED_PATHS {
+        if let Ok(guess) = base.join(path) {
+            let guess = guess.to_string();
+            if !candidates.contains(&guess) {
+                candidates.push(guess);
+            }
+        }
+    }
+    candidates
+}
+
+fn looks_like_html(text: &str) -> bool {
+    let head: String = text.trim_start().chars().take(512).collect::<String>().to_lowercase();
+    head.starts_with("<!doctype html") || head.contains("<html") || head.contains("<head")
+}
+
+/// URLs of `<link rel="alternate">` tags with an RSS or Atom type, resolved
+/// against the page URL
+fn feed_links(html: &str, base: &reqwest::Url) -> Vec<String> {
+    let Ok(selector) = Selector::parse("link[rel~=alternate i][type]") else {
+        return Vec::new();
+    };
+    let document = Html::parse_document(html);
+    let mut links = Vec::new();
+
+    for link in document.select(&selector) {
+        let element = link.value();
+        let is_feed = element.attr("type").is_some_and(|t| {
+            let t = t.to_ascii_lowercase();
+            t.contains("rss") || t.contains("atom")
+        });
+        if let (true, Some(href)) = (is_feed, element.attr("href"))
+            && let Ok(resolved) = base.join(href.trim())
+        {
+            let resolved = resolved.to_string();
+            if !links.contains(&resolved) {
+                links.push(resolved);
+            }
+        }
+    }
+    links
+}
+
+/// Describe a failed fetch for the feed's error column. Certificate problems
+/// get a clear message pointing at `allow_invalid_certs`; anything else keeps
+/// reqwest's own description.
+pub fn describe_fetch_error(error: &(dyn Error + Send + Sync + 'static)) -> String {
+    let mut source: Option<&(dyn Error + 'static)> = Some(error);
+    while let Some(err) = source {
+        let message = err.to_string();
+        if message.to_lowercase().contains("certificate") {
+            return format!(
+                "TLS certificate error: {} (set allow_invalid_certs = true on this feed's source to fetch anyway)",
+                message
+            );
+        }
+        source = err.source();
+    }
+    error.to_string()
+}
+
+const YOUTUBE_FEED_BASE: &str = "https://www.youtube.com/feeds/videos.xml";
+
+pub fn is_youtube_url(url: &str) -> bool {
+    reqwest::Url::parse(url.trim())
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.trim_start_matches("www.").trim_start_matches("m.").to_string()))
+        .is_some_and(|host| host == "youtube.com" || host == "youtu.be")
+}
+
+/// Derive the RSS feed for a YouTube channel or playlist URL.
+///
+/// Handles `/channel/<id>`, `/playlist?list=<id>`, watch URLs carrying a
+/// `list` parameter, and feed URLs (returned unchanged). Handle-style
+/// (`/@name`) and plain video URLs don't carry the channel id, so they
+/// return `None`.
+pub fn youtube_feed_url(url: &str) -> Option<String> {
+    if !is_youtube_url(url) {
+        return None;
+    }
+    let parsed = reqwest::Url::parse(url.trim()).ok()?;
+
+    if parsed.path() == "/feeds/videos.xml" {
+        return Some(parsed.to_string());
+    }
+
+    if let Some(channel_id) = parsed.path().strip_prefix("/channel/") {
+        let channel_id = channel_id.split('/').next().unwrap_or_default();
+        if !channel_id.is_empty() {
+            return Some(format!("{}?channel_id={}", YOUTUBE_FEED_BASE, channel_id));
+        }
+    }
+
+    parsed
+        .query_pairs()
+        .find(|(key, value)| key == "list" && !value.is_empty())
+        .map(|(_, playlist_id)| format!("{}?playlist_id={}", YOUTUBE_FEED_BASE, playlist_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    const RSS: &str = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel><title>Guarded</title><link>https://example.com/</link>
+<item><title>Hello</title><link>https://example.com/hello</link></item>
+</channel></rss>"#;
+
+    const BROWSER: &str = "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0";
+
+    /// A server that turns away requests without a user agent, like a bot
+    /// filter would, and serves the feed to the browser one
+    async fn guarded_server() -> (mockito::ServerGuard, mockito::Mock, mockito::Mock) {
+        let mut server = mockito::Server::new_async().await;
+        let blocked = server
+            .mock("GET", "/feed.xml")
+            .match_header("user-agent", Matcher::Missing)
+            .with_status(403)
+            .create_async()
+            .await;
+        let allowed = server
+            .mock("GET", "/feed.xml")
+            .match_header("user-agent", BROWSER)
+            .with_header("content-type", "application/rss+xml")
+            .with_body(RSS)
+            .create_async()
+            .await;
+        (server, blocked, allowed)
+    }
+
+    #[tokio::test]
+    async fn retries_a_403_with_the_fallback_user_agent() {
+        let (server, blocked, allowed) = guarded_server().await;
+        let url = format!("{}/feed.xml", server.url());
+
+        let outcome = fetch_feed_if_changed(&Client::new(), &url, &CacheHeaders::default(), None, Some(BROWSER))
+            .await
+            .unwrap();
+
+        assert!(matches!(outcome, FetchOutcome::Fetched { feed, .. } if feed.entries.len() == 1));
+        blocked.assert_async().await;
+        allowed.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn keeps_the_403_without_a_fallback_user_agent() {
+        let (server, blocked, allowed) = guarded_server().await;
+        let url = format!("{}/feed.xml", server.url());
+
+        let result = fetch_feed_if_changed(&Client::new(), &url, &CacheHeaders::default(), None, None).await;
+
+        assert!(matches!(result, Err(FetchError::Status(reqwest::StatusCode::FORBIDDEN))));
+        blocked.assert_async().await;
+        allowed.expect(0).assert_async().await;
+    }
+
+    #[test]
+    fn finds_feed_links_in_a_page() {
+        let html = r#"<!doctype html><html><head>
+            <link rel="stylesheet" href="/style.css" type="text/css">
+            <LINK REL="Alternate" TYPE="application/rss+xml" HREF="/feed.xml?a=1&amp;b=2">
+            <link href='https://example.com/atom' type='application/atom+xml' rel='alternate nofollow'>
+            <link rel="alternate" hreflang="de" href="/de/">
+            <link rel="alternate" type="application/rss+xml" href="/feed.xml?a=1&b=2">
+            </head><body></body></html>"#;
+        let base = reqwest::Url::parse("https://example.com/blog/").unwrap();
+
+        assert_eq!(
+            feed_links(html, &base),
+            vec!["https://example.com/feed.xml?a=1&b=2", "https://example.com/atom"]
+        );
+    }
 }
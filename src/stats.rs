@@ -1,8 +1,8 @@
+use crate::app::STALE_READ_LATER_DAYS;
 use crate::db::Database;
 use rusqlite::Result;
 
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
 pub struct AppStats {
     pub total_posts: usize,
     pub read_posts: usize,
@@ -10,51 +10,32 @@ pub struct AppStats {
     pub saved_posts: usize,
     pub archived_posts: usize,
     pub read_later_posts: usize,
+    pub stale_read_later_posts: usize,
     pub feeds_count: usize,
+    pub uncategorized_feeds: usize,
     pub categories: Vec<(String, usize)>,
 }
 
-impl Default for AppStats {
-    fn default() -> Self {
-        AppStats {
-            total_posts: 0,
-            read_posts: 0,
-            unread_posts: 0,
-            saved_posts: 0,
-            archived_posts: 0,
-            read_later_posts: 0,
-            feeds_count: 0,
-            categories: vec![],
-        }
-    }
-}
-
-#[allow(dead_code)]
 impl AppStats {
+    /// Two queries: the totals in one pass over the posts, then the per-category list
     pub fn from_db(db: &Database) -> Result<Self> {
-        let total_posts = db.get_count("SELECT COUNT(*) FROM posts")?;
-        let read_posts = db.get_count("SELECT COUNT(*) FROM posts WHERE is_read = 1")?;
-        let unread_posts = total_posts - read_posts;
-        let saved_posts = db.get_count("SELECT COUNT(*) FROM posts WHERE is_bookmarked = 1")?;
-        let archived_posts = db.get_count("SELECT COUNT(*) FROM posts WHERE is_archived = 1")?;
-        let read_later_posts = db.get_count("SELECT COUNT(*) FROM posts WHERE is_read_later = 1")?;
-        let feeds_count = db.get_count("SELECT COUNT(*) FROM feeds")?;
-
+        let counts = db.get_overview_counts(STALE_READ_LATER_DAYS)?;
         let categories = db.get_category_stats()?;
 
         Ok(AppStats {
-            total_posts,
-            read_posts,
-            unread_posts,
-            saved_posts,
-            archived_posts,
-            read_later_posts,
-            feeds_count,
+            total_posts: counts.total_posts,
+            read_posts: counts.read_posts,
+            unread_posts: counts.total_posts - counts.read_posts,
+            saved_posts: counts.bookmarked_posts,
+            archived_posts: counts.archived_posts,
+            read_later_posts: counts.read_later_posts,
+            stale_read_later_posts: counts.stale_read_later_posts,
+            feeds_count: counts.feeds,
+            uncategorized_feeds: counts.uncategorized_feeds,
             categories,
         })
     }
 
-    #[allow(dead_code)]
     pub fn reading_progress(&self) -> f64 {
         if self.total_posts > 0 {
             self.read_posts as f64 / self.total_posts as f64
@@ -1,5 +1,110 @@
 use ratatui::style::Color;
 
+use crate::config::ThemeConfig;
+
+/// How many colors the terminal can show; themes are defined in RGB and get
+/// mapped down when truecolor isn't available.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ColorMode {
+    #[value(name = "truecolor")]
+    TrueColor,
+    #[value(name = "256")]
+    Ansi256,
+    #[value(name = "16")]
+    Ansi16,
+}
+
+impl ColorMode {
+    /// Guess color support from `$COLORTERM` and `$TERM`
+    pub fn detect() -> Self {
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default().to_lowercase();
+        if colorterm.contains("truecolor") || colorterm.contains("24bit") {
+            return ColorMode::TrueColor;
+        }
+
+        let term = std::env::var("TERM").unwrap_or_default().to_lowercase();
+        if term.contains("256color") {
+            ColorMode::Ansi256
+        } else if term.is_empty() || term == "dumb" || term == "linux" || term.starts_with("vt") {
+            ColorMode::Ansi16
+        } else {
+            // Most modern terminals that don't advertise COLORTERM still handle 256 colors
+            ColorMode::Ansi256
+        }
+    }
+
+    pub fn adapt(&self, color: Color) -> Color {
+        match (self, color) {
+            (ColorMode::TrueColor, c) => c,
+            (ColorMode::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(rgb_to_ansi256(r, g, b)),
+            (ColorMode::Ansi16, Color::Rgb(r, g, b)) => rgb_to_ansi16(r, g, b),
+            (_, c) => c,
+        }
+    }
+}
+
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let nearest_level = |v: u8| {
+        LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, l)| (v as i32 - **l as i32).abs())
+            .map(|(i, _)| i as u8)
+            .unwrap_or(0)
+    };
+
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube = (LEVELS[ri as usize], LEVELS[gi as usize], LEVELS[bi as usize]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    // The grayscale ramp (232-255) is often closer for muted colors
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step = ((avg.saturating_sub(8)) / 10).min(23) as u8;
+    let gray = 8 + gray_step * 10;
+    let gray_index = 232 + gray_step;
+
+    if distance((r, g, b), (gray, gray, gray)) < distance((r, g, b), cube) {
+        gray_index
+    } else {
+        cube_index
+    }
+}
+
+fn rgb_to_ansi16(r: u8, g: u8, b: u8) -> Color {
+    const PALETTE: [(Color, (u8, u8, u8)); 16] = [
+        (Color::Black, (0, 0, 0)),
+        (Color::Red, (205, 0, 0)),
+        (Color::Green, (0, 205, 0)),
+        (Color::Yellow, (205, 205, 0)),
+        (Color::Blue, (0, 0, 238)),
+        (Color::Magenta, (205, 0, 205)),
+        (Color::Cyan, (0, 205, 205)),
+        (Color::Gray, (229, 229, 229)),
+        (Color::DarkGray, (127, 127, 127)),
+        (Color::LightRed, (255, 0, 0)),
+        (Color::LightGreen, (0, 255, 0)),
+        (Color::LightYellow, (255, 255, 0)),
+        (Color::LightBlue, (92, 92, 255)),
+        (Color::LightMagenta, (255, 0, 255)),
+        (Color::LightCyan, (0, 255, 255)),
+        (Color::White, (255, 255, 255)),
+    ];
+
+    PALETTE
+        .iter()
+        .min_by_key(|(_, rgb)| distance((r, g, b), *rgb))
+        .map(|(c, _)| *c)
+        .unwrap_or(Color::Reset)
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let dr = a.0 as i32 - b.0 as i32;
+    let dg = a.1 as i32 - b.1 as i32;
+    let db = a.2 as i32 - b.2 as i32;
+    (dr * dr + dg * dg + db * db) as u32
+}
+
 #[allow(dead_code)]
 pub trait Theme {
     fn base(&self) -> Color;
@@ -13,6 +118,69 @@ pub trait Theme {
     fn surface(&self) -> Color;
     fn warning(&self) -> Color;
     fn success(&self) -> Color;
+    fn error(&self) -> Color;
+}
+
+/// Wraps a theme and maps its colors down to what the terminal supports
+pub struct AdaptedTheme {
+    inner: Box<dyn Theme>,
+    mode: ColorMode,
+}
+
+impl AdaptedTheme {
+    pub fn new(inner: Box<dyn Theme>, mode: ColorMode) -> Self {
+        AdaptedTheme { inner, mode }
+    }
+}
+
+impl Theme for AdaptedTheme {
+    fn base(&self) -> Color {
+        self.mode.adapt(self.inner.base())
+    }
+
+    fn mantle(&self) -> Color {
+        self.mode.adapt(self.inner.mantle())
+    }
+
+    fn text(&self) -> Color {
+        self.mode.adapt(self.inner.text())
+    }
+
+    fn subtext(&self) -> Color {
+        self.mode.adapt(self.inner.subtext())
+    }
+
+    fn overlay(&self) -> Color {
+        self.mode.adapt(self.inner.overlay())
+    }
+
+    fn accent_primary(&self) -> Color {
+        self.mode.adapt(self.inner.accent_primary())
+    }
+
+    fn accent_secondary(&self) -> Color {
+        self.mode.adapt(self.inner.accent_secondary())
+    }
+
+    fn highlight(&self) -> Color {
+        self.mode.adapt(self.inner.highlight())
+    }
+
+    fn surface(&self) -> Color {
+        self.mode.adapt(self.inner.surface())
+    }
+
+    fn warning(&self) -> Color {
+        self.mode.adapt(self.inner.warning())
+    }
+
+    fn success(&self) -> Color {
+        self.mode.adapt(self.inner.success())
+    }
+
+    fn error(&self) -> Color {
+        self.mode.adapt(self.inner.error())
+    }
 }
 
 // Claude Code Theme - Deep navy with amber/indigo accents
@@ -62,6 +230,10 @@ impl Theme for ClaudeCodeTheme {
     fn success(&self) -> Color {
         Color::Rgb(16, 185, 129) // #10b981 - emerald green
     }
+
+    fn error(&self) -> Color {
+        Color::Rgb(239, 68, 68) // #ef4444 - red
+    }
 }
 
 // Catppuccin Mocha Theme - Green accent version (matching quit tracker)
@@ -111,6 +283,217 @@ impl Theme for CatppuccinMochaTheme {
     fn success(&self) -> Color {
         Color::Rgb(166, 227, 161) // #a6e3a1 - green (success/progress)
     }
+
+    fn error(&self) -> Color {
+        Color::Rgb(243, 139, 168) // #f38ba8 - red
+    }
+}
+
+// Gruvbox Theme - dark, medium contrast
+pub struct GruvboxTheme;
+
+impl Theme for GruvboxTheme {
+    fn base(&self) -> Color {
+        Color::Rgb(40, 40, 40) // #282828 - bg0
+    }
+
+    fn mantle(&self) -> Color {
+        Color::Rgb(29, 32, 33) // #1d2021 - bg0_h, darker background
+    }
+
+    fn text(&self) -> Color {
+        Color::Rgb(235, 219, 178) // #ebdbb2 - fg1
+    }
+
+    fn subtext(&self) -> Color {
+        Color::Rgb(189, 174, 147) // #bdae93 - fg3, muted text
+    }
+
+    fn overlay(&self) -> Color {
+        Color::Rgb(146, 131, 116) // #928374 - gray, dimmed text
+    }
+
+    fn accent_primary(&self) -> Color {
+        Color::Rgb(250, 189, 47) // #fabd2f - yellow
+    }
+
+    fn accent_secondary(&self) -> Color {
+        Color::Rgb(131, 165, 152) // #83a598 - blue (active highlights)
+    }
+
+    fn highlight(&self) -> Color {
+        Color::Rgb(60, 56, 54) // #3c3836 - bg1 (selection background)
+    }
+
+    fn surface(&self) -> Color {
+        Color::Rgb(60, 56, 54) // #3c3836 - bg1
+    }
+
+    fn warning(&self) -> Color {
+        Color::Rgb(254, 128, 25) // #fe8019 - orange
+    }
+
+    fn success(&self) -> Color {
+        Color::Rgb(184, 187, 38) // #b8bb26 - green
+    }
+
+    fn error(&self) -> Color {
+        Color::Rgb(251, 73, 52) // #fb4934 - red
+    }
+}
+
+// Nord Theme - arctic polar night with frost accents
+pub struct NordTheme;
+
+impl Theme for NordTheme {
+    fn base(&self) -> Color {
+        Color::Rgb(46, 52, 64) // #2e3440 - nord0, polar night
+    }
+
+    fn mantle(&self) -> Color {
+        Color::Rgb(36, 41, 51) // #242933 - darker than nord0
+    }
+
+    fn text(&self) -> Color {
+        Color::Rgb(236, 239, 244) // #eceff4 - nord6, snow storm
+    }
+
+    fn subtext(&self) -> Color {
+        Color::Rgb(216, 222, 233) // #d8dee9 - nord4, muted text
+    }
+
+    fn overlay(&self) -> Color {
+        Color::Rgb(123, 136, 161) // #7b88a1 - brightened nord3, dimmed text
+    }
+
+    fn accent_primary(&self) -> Color {
+        Color::Rgb(136, 192, 208) // #88c0d0 - nord8, frost cyan
+    }
+
+    fn accent_secondary(&self) -> Color {
+        Color::Rgb(129, 161, 193) // #81a1c1 - nord9 (active highlights)
+    }
+
+    fn highlight(&self) -> Color {
+        Color::Rgb(59, 66, 82) // #3b4252 - nord1 (selection background)
+    }
+
+    fn surface(&self) -> Color {
+        Color::Rgb(67, 76, 94) // #434c5e - nord2
+    }
+
+    fn warning(&self) -> Color {
+        Color::Rgb(235, 203, 139) // #ebcb8b - nord13, yellow
+    }
+
+    fn success(&self) -> Color {
+        Color::Rgb(163, 190, 140) // #a3be8c - nord14, green
+    }
+
+    fn error(&self) -> Color {
+        Color::Rgb(191, 97, 106) // #bf616a - nord11, aurora red
+    }
+}
+
+/// Parse a `#rrggbb` hex string (the `#` is optional)
+pub fn parse_hex_color(value: &str) -> Option<Color> {
+    let hex = value.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+// Custom Theme - user palette from the [theme] config section
+pub struct CustomTheme {
+    base: Color,
+    mantle: Color,
+    text: Color,
+    subtext: Color,
+    overlay: Color,
+    accent_primary: Color,
+    accent_secondary: Color,
+    highlight: Color,
+    surface: Color,
+    warning: Color,
+    success: Color,
+    error: Color,
+}
+
+impl CustomTheme {
+    /// Build from config, taking Claude Code's color for anything missing or malformed
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let fallback = ClaudeCodeTheme;
+        let pick = |value: &Option<String>, default: Color| {
+            value.as_deref().and_then(parse_hex_color).unwrap_or(default)
+        };
+        CustomTheme {
+            base: pick(&config.base, fallback.base()),
+            mantle: pick(&config.mantle, fallback.mantle()),
+            text: pick(&config.text, fallback.text()),
+            subtext: pick(&config.subtext, fallback.subtext()),
+            overlay: pick(&config.overlay, fallback.overlay()),
+            accent_primary: pick(&config.accent_primary, fallback.accent_primary()),
+            accent_secondary: pick(&config.accent_secondary, fallback.accent_secondary()),
+            highlight: pick(&config.highlight, fallback.highlight()),
+            surface: pick(&config.surface, fallback.surface()),
+            warning: pick(&config.warning, fallback.warning()),
+            success: pick(&config.success, fallback.success()),
+            error: pick(&config.error, fallback.error()),
+        }
+    }
+}
+
+impl Theme for CustomTheme {
+    fn base(&self) -> Color {
+        self.base
+    }
+
+    fn mantle(&self) -> Color {
+        self.mantle
+    }
+
+    fn text(&self) -> Color {
+        self.text
+    }
+
+    fn subtext(&self) -> Color {
+        self.subtext
+    }
+
+    fn overlay(&self) -> Color {
+        self.overlay
+    }
+
+    fn accent_primary(&self) -> Color {
+        self.accent_primary
+    }
+
+    fn accent_secondary(&self) -> Color {
+        self.accent_secondary
+    }
+
+    fn highlight(&self) -> Color {
+        self.highlight
+    }
+
+    fn surface(&self) -> Color {
+        self.surface
+    }
+
+    fn warning(&self) -> Color {
+        self.warning
+    }
+
+    fn success(&self) -> Color {
+        self.success
+    }
+
+    fn error(&self) -> Color {
+        self.error
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -118,21 +501,49 @@ impl Theme for CatppuccinMochaTheme {
 pub enum ThemeVariant {
     ClaudeCode,
     CatppuccinMocha,
+    Gruvbox,
+    Nord,
+    Custom,
 }
 
 #[allow(dead_code)]
 impl ThemeVariant {
-    pub fn get_theme(&self) -> Box<dyn Theme> {
+    /// `custom` is the `[theme]` palette, only read by the custom variant
+    pub fn get_theme(&self, custom: &ThemeConfig) -> Box<dyn Theme> {
         match self {
             ThemeVariant::ClaudeCode => Box::new(ClaudeCodeTheme),
             ThemeVariant::CatppuccinMocha => Box::new(CatppuccinMochaTheme),
+            ThemeVariant::Gruvbox => Box::new(GruvboxTheme),
+            ThemeVariant::Nord => Box::new(NordTheme),
+            ThemeVariant::Custom => Box::new(CustomTheme::from_config(custom)),
         }
     }
 
+    /// The theme after this one when cycling. Custom is skipped unless a
+    /// `[theme]` palette is configured, since it would look like Claude Code.
+    pub fn next(&self, custom: &ThemeConfig) -> Self {
+        let order = [
+            ThemeVariant::ClaudeCode,
+            ThemeVariant::CatppuccinMocha,
+            ThemeVariant::Gruvbox,
+            ThemeVariant::Nord,
+            ThemeVariant::Custom,
+        ];
+        let available: Vec<&ThemeVariant> = order
+            .iter()
+            .filter(|v| **v != ThemeVariant::Custom || custom.has_colors())
+            .collect();
+        let current = available.iter().position(|v| *v == self).unwrap_or(0);
+        available[(current + 1) % available.len()].clone()
+    }
+
     pub fn from_str(s: &str) -> Self {
         match s {
             "claude-code" => ThemeVariant::ClaudeCode,
             "catppuccin-mocha" => ThemeVariant::CatppuccinMocha,
+            "gruvbox" => ThemeVariant::Gruvbox,
+            "nord" => ThemeVariant::Nord,
+            "custom" => ThemeVariant::Custom,
             _ => ThemeVariant::ClaudeCode, // Default to Claude Code
         }
     }
@@ -141,6 +552,9 @@ impl ThemeVariant {
         match self {
             ThemeVariant::ClaudeCode => "claude-code",
             ThemeVariant::CatppuccinMocha => "catppuccin-mocha",
+            ThemeVariant::Gruvbox => "gruvbox",
+            ThemeVariant::Nord => "nord",
+            ThemeVariant::Custom => "custom",
         }
     }
 }
@@ -1,18 +1,27 @@
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{
+        Block, Borders, Clear, Gauge, LineGauge, List, ListItem, ListState, Paragraph, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Wrap,
+    },
     Frame,
 };
 
-use crate::app::{App, InputMode};
+use crate::app::{App, InputMode, SubFilter, FAILING_FEED_STREAK, STALE_READ_LATER_DAYS};
+use crate::ascii_art::{get_random_quote, NEWS_BANNER};
+use crate::db::{Post, UNCATEGORIZED};
+use crate::metrics::DebugMetrics;
 use crate::navigation::{FocusPane, NavNode, SidebarSection, SmartView};
-use crate::theme::{Theme, ThemeVariant};
+use crate::relative_time::{format_post_date, format_relative};
+use crate::rss::feed_host;
+use crate::theme::{AdaptedTheme, Theme};
 
-pub fn ui(f: &mut Frame, app: &mut App, theme_name: &str) {
-    let theme_variant = ThemeVariant::from_str(theme_name);
-    let theme = theme_variant.get_theme();
+const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+pub fn ui(f: &mut Frame, app: &mut App) {
+    let theme = AdaptedTheme::new(app.theme.get_theme(&app.config.theme), app.color_mode);
 
     let size = f.area();
     let block = Block::default().style(Style::default().bg(theme.base()));
@@ -20,31 +29,62 @@ pub fn ui(f: &mut Frame, app: &mut App, theme_name: &str) {
 
     match &app.input_mode {
         InputMode::Welcome => {
-            draw_welcome(f, app, size, &*theme);
+            draw_welcome(f, app, size, &theme);
+            return;
+        }
+        InputMode::ImportingOpml(None) => {
+            draw_welcome(f, app, size, &theme);
+            draw_opml_import_modal(f, app, size, &theme);
             return;
         }
         InputMode::Help => {
-            draw_main_layout(f, app, size, &*theme);
-            draw_help_overlay(f, size, &*theme);
+            draw_main_layout(f, app, size, &theme);
+            draw_help_overlay(f, size, &theme);
+            return;
+        }
+        InputMode::Dashboard => {
+            draw_dashboard(f, app, size, &theme);
             return;
         }
         _ => {}
     }
 
-    draw_main_layout(f, app, size, &*theme);
+    draw_main_layout(f, app, size, &theme);
+
+    if let Some(metrics) = app.debug.as_ref().filter(|m| m.visible) {
+        draw_debug_overlay(f, app, metrics, size, &theme);
+    }
 
     match &app.input_mode {
-        InputMode::AddingFeed => draw_input_modal(f, app, size, &*theme, "Add Feed URL"),
-        InputMode::AddingCategory => draw_input_modal(f, app, size, &*theme, "Add Category"),
-        InputMode::SelectingCategory => draw_category_selector(f, app, size, &*theme),
-        InputMode::EditingCategoryFeeds(cat) => draw_category_feeds_editor(f, app, size, &*theme, cat),
+        InputMode::AddingFeed => draw_input_modal(f, app, size, &theme, "Add Feed URL"),
+        InputMode::AddingCategory => draw_input_modal(f, app, size, &theme, "Add Category"),
+        InputMode::Searching => draw_input_modal(f, app, size, &theme, "Search Posts"),
+        InputMode::SelectingCategory => draw_category_selector(f, app, size, &theme, " Select Category "),
+        InputMode::ChoosingDiscoveredFeed => draw_discovered_feeds(f, app, size, &theme),
+        InputMode::MovingCategoryFeeds(from) => {
+            draw_category_selector(f, app, size, &theme, &format!(" Move '{}' feeds to ", from))
+        }
+        InputMode::EditingCategoryFeeds(cat) => draw_category_feeds_editor(f, app, size, &theme, cat),
+        InputMode::ImportingOpml(Some(cat)) => {
+            draw_category_feeds_editor(f, app, size, &theme, cat);
+            draw_opml_import_modal(f, app, size, &theme);
+        }
         InputMode::Confirming(action) => {
             let msg = match action {
-                crate::app::ConfirmAction::DeletePost(_) => "Delete this post?",
-                crate::app::ConfirmAction::DeleteFeed(_) => "Delete this feed and all its posts?",
-                crate::app::ConfirmAction::DeleteCategory(_) => "Delete this category?",
+                crate::app::ConfirmAction::DeletePost(_) => "Delete this post?".to_string(),
+                crate::app::ConfirmAction::DeleteFeed(_) => "Delete this feed and all its posts?".to_string(),
+                crate::app::ConfirmAction::DeleteCategory(_) => "Delete this category?".to_string(),
+                crate::app::ConfirmAction::DeleteCategoryPosts(_) => {
+                    "Delete all non-starred posts in this category?".to_string()
+                }
+                crate::app::ConfirmAction::MoveFeed { from, to, .. } => {
+                    format!("Already subscribed in '{}'. Move it to '{}'?", from, to)
+                }
+                crate::app::ConfirmAction::UseDiscoveredFeed { url, .. } => {
+                    format!("No feed at that address, but the page links to {}. Add it?", url)
+                }
             };
-            draw_confirm_modal(f, size, &*theme, msg);
+            draw_confirm_modal(f, size, &theme, &msg);
         }
         _ => {}
     }
@@ -83,14 +123,25 @@ fn draw_header(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
 
     let title = format!(" 󰑫 News Reader{} ", loading_indicator);
 
-    let header = Paragraph::new(Line::from(vec![
+    let mut spans = vec![
         Span::styled(title, Style::default().fg(theme.accent_primary()).add_modifier(Modifier::BOLD)),
         Span::raw("  "),
         Span::styled(
             format!("[{}]", app.active_node.title()),
             Style::default().fg(theme.accent_secondary()),
         ),
-    ]))
+    ];
+
+    if app.unhealthy_feeds > 0 {
+        let noun = if app.unhealthy_feeds == 1 { "feed" } else { "feeds" };
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            format!("󰀦 {} {} unhealthy", app.unhealthy_feeds, noun),
+            Style::default().fg(theme.warning()),
+        ));
+    }
+
+    let header = Paragraph::new(Line::from(spans))
     .block(
         Block::default()
             .borders(Borders::ALL)
@@ -100,7 +151,159 @@ fn draw_header(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
     f.render_widget(header, area);
 }
 
-fn draw_sidebar(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
+/// Below this height the dashboard drops the banner and quote and packs the stats into single lines
+const COMPACT_DASHBOARD_HEIGHT: u16 = 30;
+
+fn draw_dashboard(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
+    let stats = &app.stats;
+    let compact = area.height < COMPACT_DASHBOARD_HEIGHT;
+
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent_primary()))
+        .title(" 󰕮 Dashboard ")
+        .title_style(Style::default().fg(theme.accent_secondary()).add_modifier(Modifier::BOLD))
+        .title_bottom(Line::styled(
+            if app.show_banner { " b:Hide banner · any key closes " } else { " b:Show banner · any key closes " },
+            Style::default().fg(theme.subtext()),
+        ));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let categories: Vec<ListItem> = stats
+        .categories
+        .iter()
+        .map(|(name, count)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("  {:<24}", name), Style::default().fg(theme.text())),
+                Span::styled(format!("{:>6} posts", count), Style::default().fg(theme.subtext())),
+            ]))
+        })
+        .collect();
+    let notes = Paragraph::new(Span::styled(
+        format!(
+            " {} uncategorized feeds · {} Read Later saves older than {} days",
+            stats.uncategorized_feeds, stats.stale_read_later_posts, STALE_READ_LATER_DAYS
+        ),
+        Style::default().fg(theme.subtext()),
+    ));
+
+    let categories_list = List::new(categories).block(
+        Block::default()
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(theme.overlay()))
+            .title(" Categories ")
+            .title_style(Style::default().fg(theme.accent_primary())),
+    );
+
+    if compact {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ])
+            .split(inner);
+
+        let summary = Paragraph::new(Line::from(vec![
+            Span::styled(format!(" {} unread", stats.unread_posts), Style::default().fg(theme.accent_primary())),
+            Span::styled(
+                format!(
+                    " · {} starred · {} later · {} archived · {} feeds",
+                    stats.saved_posts, stats.read_later_posts, stats.archived_posts, stats.feeds_count
+                ),
+                Style::default().fg(theme.text()),
+            ),
+        ]));
+        f.render_widget(summary, chunks[0]);
+
+        let progress = LineGauge::default()
+            .filled_style(Style::default().fg(theme.accent_secondary()))
+            .unfilled_style(Style::default().fg(theme.overlay()))
+            .label(format!(" Read {}/{} ", stats.read_posts, stats.total_posts))
+            .ratio(stats.reading_progress());
+        f.render_widget(progress, chunks[1]);
+        f.render_widget(notes, chunks[2]);
+        f.render_widget(categories_list, chunks[3]);
+        return;
+    }
+
+    // A hidden banner gets a zero-height row, leaving its space to the categories list
+    let banner_height = if app.show_banner {
+        NEWS_BANNER.lines().count() as u16
+    } else {
+        0
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(banner_height),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    if app.show_banner {
+        let banner = Paragraph::new(NEWS_BANNER)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.accent_primary()));
+        f.render_widget(banner, chunks[0]);
+    }
+
+    let counts = [
+        ("Unread", stats.unread_posts),
+        ("Starred", stats.saved_posts),
+        ("Read Later", stats.read_later_posts),
+        ("Archived", stats.archived_posts),
+        ("Feeds", stats.feeds_count),
+    ];
+    let count_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, counts.len() as u32); 5])
+        .split(chunks[1]);
+    for ((label, count), chunk) in counts.iter().zip(count_chunks.iter()) {
+        let card = Paragraph::new(Span::styled(
+            count.to_string(),
+            Style::default().fg(theme.text()).add_modifier(Modifier::BOLD),
+        ))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.overlay()))
+                .title(format!(" {} ", label))
+                .title_style(Style::default().fg(theme.subtext())),
+        );
+        f.render_widget(card, *chunk);
+    }
+
+    let progress = Gauge::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.overlay()))
+                .title(" Reading progress ")
+                .title_style(Style::default().fg(theme.subtext())),
+        )
+        .gauge_style(Style::default().fg(theme.accent_secondary()).bg(theme.mantle()))
+        .label(format!("{}/{} read", stats.read_posts, stats.total_posts))
+        .ratio(stats.reading_progress());
+    f.render_widget(progress, chunks[2]);
+    f.render_widget(notes, chunks[3]);
+    f.render_widget(categories_list, chunks[4]);
+
+    let quote = Paragraph::new(get_random_quote())
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.subtext()).add_modifier(Modifier::ITALIC));
+    f.render_widget(quote, chunks[5]);
+}
+
+fn draw_sidebar(f: &mut Frame, app: &mut App, area: Rect, theme: &dyn Theme) {
     let is_focused = matches!(app.focus, FocusPane::Sidebar);
     let border_color = if is_focused {
         theme.accent_primary()
@@ -116,13 +319,21 @@ fn draw_sidebar(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
 
     let inner = block.inner(area);
     f.render_widget(block, area);
+    app.sidebar_area = inner;
 
     let mut items: Vec<ListItem> = Vec::new();
 
-    items.push(ListItem::new(Line::from(Span::styled(
-        "VIEWS",
-        Style::default().fg(theme.subtext()).add_modifier(Modifier::BOLD),
-    ))));
+    // The header of the section holding the cursor lights up while the sidebar has focus
+    let header_style = |section: SidebarSection| {
+        let color = if is_focused && app.sidebar.section == section {
+            theme.accent_primary()
+        } else {
+            theme.subtext()
+        };
+        Style::default().fg(color).add_modifier(Modifier::BOLD)
+    };
+
+    items.push(ListItem::new(Line::from(Span::styled("VIEWS", header_style(SidebarSection::SmartViews)))));
 
     for (i, sv) in app.sidebar.smart_views.iter().enumerate() {
         let is_selected = matches!(app.sidebar.section, SidebarSection::SmartViews)
@@ -152,7 +363,7 @@ fn draw_sidebar(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
     items.push(ListItem::new(Line::from("")));
     items.push(ListItem::new(Line::from(Span::styled(
         "CATEGORIES",
-        Style::default().fg(theme.subtext()).add_modifier(Modifier::BOLD),
+        header_style(SidebarSection::Categories),
     ))));
 
     for (i, cat) in app.sidebar.categories.iter().enumerate() {
@@ -163,24 +374,27 @@ fn draw_sidebar(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
         let count = app.sidebar.get_count(&NavNode::Category(cat.clone()));
         let is_active = app.active_node == NavNode::Category(cat.clone());
 
+        let is_uncategorized = cat == UNCATEGORIZED;
         let prefix = if is_active { "▶ " } else { "  " };
         let style = if is_selected {
             Style::default().fg(theme.accent_secondary()).add_modifier(Modifier::BOLD)
         } else if is_active {
             Style::default().fg(theme.accent_primary())
+        } else if is_uncategorized {
+            Style::default().fg(theme.subtext()).add_modifier(Modifier::ITALIC)
         } else {
             Style::default().fg(theme.text())
         };
 
-        let display_name = if cat.len() > 12 {
-            format!("{}…", &cat[..11])
+        let display_name = if cat.chars().count() > 12 {
+            format!("{}…", cat.chars().take(11).collect::<String>())
         } else {
             cat.clone()
         };
 
         items.push(ListItem::new(Line::from(vec![
             Span::styled(prefix, style),
-            Span::styled("󰉋 ", style),
+            Span::styled(if is_uncategorized { "󰉗 " } else { "󰉋 " }, style),
             Span::styled(format!("{} ", display_name), style),
             Span::styled(format!("({})", count), Style::default().fg(theme.subtext())),
         ])));
@@ -190,7 +404,9 @@ fn draw_sidebar(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
     f.render_widget(list, inner);
 }
 
-fn draw_posts_list(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
+fn draw_posts_list(f: &mut Frame, app: &mut App, area: Rect, theme: &dyn Theme) {
+    app.posts_area = area;
+    app.posts_offset = 0;
     let is_focused = matches!(app.focus, FocusPane::Posts);
     let border_color = if is_focused {
         theme.accent_primary()
@@ -198,15 +414,38 @@ fn draw_posts_list(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
         theme.overlay()
     };
 
-    let title = format!(
-        " {} ({}) ",
-        app.active_node.title(),
-        app.posts.len()
-    );
+    let is_read_later_view = app.active_node == NavNode::SmartView(SmartView::ReadLater);
+    let mut node_title = if let Some(term) = &app.search_term {
+        format!("Search \"{}\"", term)
+    } else if app.sub_filter != SubFilter::All && matches!(app.active_node, NavNode::SmartView(_)) {
+        format!("{} [{}]", app.active_node.title(), app.sub_filter.label())
+    } else {
+        app.active_node.title()
+    };
+    if let Some(filter) = &app.list_filter {
+        node_title.push_str(&format!(" · filter \"{}\"", filter));
+    }
+    let title = if is_read_later_view && app.stale_read_later > 0 {
+        format!(
+            " {} ({}) · {} saved over {} days ago ",
+            node_title,
+            app.posts.len(),
+            app.stale_read_later,
+            STALE_READ_LATER_DAYS
+        )
+    } else {
+        format!(
+            " {} ({}) ",
+            node_title,
+            app.posts.len()
+        )
+    };
 
     if app.posts.is_empty() {
         let empty_msg = match &app.active_node {
+            _ if app.filter_active() => "No loaded posts match the filter.",
             NavNode::SmartView(SmartView::Fresh) => "All caught up! No unread posts.",
+            NavNode::SmartView(SmartView::AllFeeds) => "No posts yet. Press 'r' to refresh.",
             NavNode::SmartView(SmartView::Starred) => "No starred posts yet. Press 'b' to star.",
             NavNode::SmartView(SmartView::ReadLater) => "No posts saved for later. Press 'l' to save.",
             NavNode::SmartView(SmartView::Archived) => "No archived posts.",
@@ -242,42 +481,51 @@ fn draw_posts_list(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
         .map(|(i, post)| {
             let is_selected = i == app.selected_index && is_focused;
 
-            let read_indicator = if post.is_read { "○" } else { "●" };
+            // Unread posts already scrolled past sit between unread and read
+            let is_seen = !post.is_read && app.is_seen(post);
+            let read_indicator = if post.is_read {
+                "○"
+            } else if is_seen {
+                "◐"
+            } else {
+                "●"
+            };
             let read_style = if post.is_read {
                 Style::default().fg(theme.overlay())
+            } else if is_seen {
+                Style::default().fg(theme.subtext())
             } else {
                 Style::default().fg(theme.accent_primary())
             };
 
-            let mut badges = String::new();
-            if post.is_bookmarked {
-                badges.push_str(" ★");
-            }
-            if post.is_read_later {
-                badges.push_str(" 󰃰");
-            }
-            if post.is_archived {
-                badges.push_str(" 󰆧");
-            }
+            let badges: String = post_badges(post, app.config.ui.ascii_badges)
+                .iter()
+                .map(|badge| format!(" {}", badge))
+                .collect();
 
-            let title_max_len = (area.width as usize).saturating_sub(25);
-            let title = if post.title.len() > title_max_len {
-                format!("{}…", &post.title[..title_max_len.saturating_sub(1)])
+            let reading = post
+                .reading_minutes
+                .map(|minutes| format!("{}m ", minutes))
+                .unwrap_or_default();
+
+            let title_max_len = (area.width as usize).saturating_sub(35);
+            let title = if post.title.chars().count() > title_max_len {
+                format!("{}…", post.title.chars().take(title_max_len.saturating_sub(1)).collect::<String>())
             } else {
                 post.title.clone()
             };
 
-            let date = post
-                .pub_date
-                .map(|d| d.format("%m/%d").to_string())
-                .unwrap_or_default();
+            let date = match post.read_later_at {
+                Some(saved) if is_read_later_view => format!("saved {}", format_relative(saved)),
+                _ => post.pub_date.map(format_post_date).unwrap_or_default(),
+            };
 
             let feed = post
                 .feed_title
                 .as_ref()
                 .map(|t| {
-                    if t.len() > 10 {
-                        format!("{}…", &t[..9])
+                    if t.chars().count() > 10 {
+                        format!("{}…", t.chars().take(9).collect::<String>())
                     } else {
                         t.clone()
                     }
@@ -288,6 +536,8 @@ fn draw_posts_list(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
                 Style::default().fg(theme.accent_secondary()).add_modifier(Modifier::BOLD)
             } else if post.is_read {
                 Style::default().fg(theme.subtext())
+            } else if is_seen {
+                Style::default().fg(theme.text()).add_modifier(Modifier::DIM)
             } else {
                 Style::default().fg(theme.text())
             };
@@ -300,6 +550,7 @@ fn draw_posts_list(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
                 Span::styled(title, title_style),
                 Span::styled(badges, Style::default().fg(theme.warning())),
                 Span::styled(format!("  {} ", date), Style::default().fg(theme.overlay())),
+                Span::styled(reading, Style::default().fg(theme.overlay())),
                 Span::styled(format!("[{}]", feed), Style::default().fg(theme.subtext())),
             ]))
         })
@@ -320,9 +571,32 @@ fn draw_posts_list(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
         state.select(Some(app.selected_index));
     }
     f.render_stateful_widget(list, area, &mut state);
+    app.posts_offset = state.offset();
+
+    let visible = area.height.saturating_sub(2) as usize;
+    if app.posts.len() > visible {
+        let max_offset = app.posts.len() - visible;
+        let mut scrollbar_state = ScrollbarState::new(max_offset + 1)
+            .viewport_content_length(visible)
+            .position(app.posts_offset.min(max_offset));
+        draw_scrollbar(f, area, &mut scrollbar_state, theme);
+    }
+}
+
+/// Vertical scrollbar drawn over the right border of a bordered widget
+fn draw_scrollbar(f: &mut Frame, area: Rect, state: &mut ScrollbarState, theme: &dyn Theme) {
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_style(Style::default().fg(theme.surface()))
+        .thumb_style(Style::default().fg(theme.overlay()));
+    f.render_stateful_widget(scrollbar, area.inner(Margin { vertical: 1, horizontal: 0 }), state);
 }
 
-fn draw_article_fullscreen(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
+/// html2text width used when article wrapping is off
+const UNWRAPPED_ARTICLE_WIDTH: usize = 1000;
+
+fn draw_article_fullscreen(f: &mut Frame, app: &mut App, area: Rect, theme: &dyn Theme) {
     let Some(post) = app.posts.get(app.selected_index) else {
         return;
     };
@@ -342,22 +616,23 @@ fn draw_article_fullscreen(f: &mut Frame, app: &App, area: Rect, theme: &dyn The
     // Calculate content width for html2text
     let content_width = padded_area.width.saturating_sub(4) as usize;
     
-    let content = post.content.as_deref().unwrap_or("No content available.");
-    let text_content = html2text::from_read(content.as_bytes(), content_width.max(40))
+    let full = post.content.as_deref().filter(|c| !c.trim().is_empty());
+    let summary = post.summary.as_deref();
+    let live_page = app.live_pages.get(&post.id).filter(|_| app.show_live_page);
+    let content = match live_page {
+        Some(live_page) => Some(live_page.as_str()),
+        None if app.show_summary => summary.or(full),
+        None => full.or(summary),
+    }
+    .unwrap_or("No content available.");
+    // Unwrapped mode renders at a very wide width so code blocks keep their lines
+    let render_width = if app.article_wrap { content_width.max(40) } else { UNWRAPPED_ARTICLE_WIDTH };
+    let text_content = html2text::from_read(content.as_bytes(), render_width)
         .unwrap_or_else(|_| content.to_string());
 
     let styled_lines = parse_content_to_styled_lines(&text_content, theme);
 
-    let mut title_badges = Vec::new();
-    if post.is_bookmarked {
-        title_badges.push("★");
-    }
-    if post.is_read_later {
-        title_badges.push("󰃰");
-    }
-    if post.is_archived {
-        title_badges.push("󰆧");
-    }
+    let title_badges = post_badges(post, app.config.ui.ascii_badges);
 
     let title_text = if title_badges.is_empty() {
         post.title.clone()
@@ -372,16 +647,49 @@ fn draw_article_fullscreen(f: &mut Frame, app: &App, area: Rect, theme: &dyn The
         .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
         .unwrap_or_default();
 
+    let mut meta = format!("󰉋 {}", feed_name);
+    if let Some(host) = feed_host(&post.url) {
+        // Once the text comes from the web page, say so next to where it came from
+        if app.fulltext_loaded.contains(&post.id) && !app.show_summary {
+            meta.push_str(&format!("  │  󰖟 full text from {}", host));
+        } else {
+            meta.push_str(&format!("  │  󰖟 {}", host));
+        }
+    }
+    meta.push_str(&format!("  │  󰃰 {}", date));
+    if let Some(minutes) = post.reading_minutes {
+        meta.push_str(&format!("  │  {} min read", minutes));
+    }
+    if app.fulltext_loading.contains(&post.id) {
+        meta.push_str("  │  󰑓 Fetching full article…");
+    }
+    if live_page.is_some() {
+        meta.push_str("  │  󰖟 live page (not saved)");
+    }
+
     let mut all_lines = vec![
         Line::from(Span::styled(
-            format!("󰉋 {}  │  󰃰 {}", feed_name, date),
+            meta,
             Style::default().fg(theme.subtext()),
         )),
         Line::from(""),
     ];
     all_lines.extend(styled_lines);
 
-    let paragraph = Paragraph::new(all_lines)
+    // Lines are pre-wrapped by html2text, so only those wider than the pane wrap again
+    let inner_width = content_width.max(1);
+    let wrapped_lines: usize = if app.article_wrap {
+        all_lines
+            .iter()
+            .map(|line| line.width().div_ceil(inner_width).max(1))
+            .sum()
+    } else {
+        all_lines.len()
+    };
+    app.article_lines = wrapped_lines.min(u16::MAX as usize) as u16;
+    app.article_view_height = padded_area.height.saturating_sub(2);
+
+    let mut paragraph = Paragraph::new(all_lines)
         .block(
             Block::default()
                 .borders(Borders::ALL)
@@ -390,10 +698,21 @@ fn draw_article_fullscreen(f: &mut Frame, app: &App, area: Rect, theme: &dyn The
                 .title_style(Style::default().fg(theme.accent_secondary()).add_modifier(Modifier::BOLD))
                 .padding(ratatui::widgets::Padding::horizontal(1)),
         )
-        .wrap(Wrap { trim: true })
-        .scroll((app.scroll_offset, 0));
+        .scroll((app.scroll_offset, app.h_scroll_offset));
+    if app.article_wrap {
+        paragraph = paragraph.wrap(Wrap { trim: true });
+    }
 
     f.render_widget(paragraph, padded_area);
+
+    if app.article_lines > app.article_view_height {
+        // The scrollable range ends once the last line reaches the bottom
+        let max_offset = app.article_lines.saturating_sub(app.article_view_height) as usize;
+        let mut scrollbar_state = ScrollbarState::new(max_offset + 1)
+            .viewport_content_length(app.article_view_height as usize)
+            .position((app.scroll_offset as usize).min(max_offset));
+        draw_scrollbar(f, padded_area, &mut scrollbar_state, theme);
+    }
 }
 
 fn draw_status_bar(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
@@ -402,22 +721,52 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
     } else {
         match (&app.input_mode, &app.focus) {
             (InputMode::Normal, FocusPane::Sidebar) => {
-                " h/l:Focus │ j/k:Nav │ Enter:Select │ a:Add Feed │ n:New Cat │ e:Edit Feeds │ d:Del │ ? ".to_string()
+                " h/l:Focus │ j/k:Nav │ Enter:Select │ a:Add Feed │ n:New Cat │ e:Edit Feeds │ m:Move Feeds │ d:Del │ ? ".to_string()
+            }
+            (InputMode::Normal, FocusPane::Posts) if app.search_term.is_some() => {
+                format!(
+                    " 󰍉 Search: \"{}\" │ Esc:Clear │ j/k:Nav │ Enter:Read │ /:New search ",
+                    app.search_term.as_deref().unwrap_or_default()
+                )
+            }
+            (InputMode::Normal, FocusPane::Posts) if app.list_filter.is_some() => {
+                " F:Edit filter │ Esc:Clear filter │ j/k:Nav │ Enter:Read ".to_string()
             }
             (InputMode::Normal, FocusPane::Posts) => {
-                " h/l:Focus │ j/k:Nav │ Enter:Read │ b:Star │ l:Later │ m:Read │ d:Del │ r:Refresh ".to_string()
+                format!(
+                    " h/l:Focus │ j/k:Nav │ Enter:Read │ b:Star │ l:Later │ m:Read │ d:Del │ r:Refresh │ s:Sort ({}) ",
+                    app.sort_mode.label()
+                )
+            }
+            (InputMode::Normal, FocusPane::Article) if app.auto_scroll.is_some() => {
+                let state = if app.is_auto_scrolling() { "Auto-scrolling" } else { "Auto-scroll paused" };
+                format!(" {} │ Space:Pause/Resume │ A:Stop │ j/k:Scroll │ Esc:Back ", state)
+            }
+            (InputMode::Normal, FocusPane::Article)
+                if app.posts.get(app.selected_index).is_some_and(|p| p.enclosure_url.is_some()) =>
+            {
+                " Esc:Back │ j/k:Scroll │ J/K:Next/Prev │ p:Play episode │ b:Star │ l:Later │ o:Browser │ y:Copy URL ".to_string()
             }
             (InputMode::Normal, FocusPane::Article) => {
-                " Esc:Back │ j/k:Scroll │ b:Star │ l:Later │ a:Archive │ o:Browser │ y:Copy URL ".to_string()
+                " Esc:Back │ j/k:Scroll │ J/K:Next/Prev │ b:Star │ l:Later │ a:Archive │ f:Full text │ L:Live page │ o:Browser │ y:Copy URL ".to_string()
             }
-            (InputMode::AddingFeed, _) | (InputMode::AddingCategory, _) => {
+            (InputMode::Filtering, _) => {
+                let (before_cursor, after_cursor) = app.text_input.split_at_cursor();
+                format!(" Filter: {}█{} │ Enter:Keep │ Esc:Clear ", before_cursor, after_cursor)
+            }
+            (InputMode::AddingFeed, _)
+            | (InputMode::AddingCategory, _)
+            | (InputMode::Searching, _)
+            | (InputMode::ImportingOpml(_), _) => {
                 " Type text │ Enter:Confirm │ Esc:Cancel ".to_string()
             }
-            (InputMode::SelectingCategory, _) => {
+            (InputMode::SelectingCategory, _)
+            | (InputMode::MovingCategoryFeeds(_), _)
+            | (InputMode::ChoosingDiscoveredFeed, _) => {
                 " j/k:Navigate │ Enter:Select │ Esc:Cancel ".to_string()
             }
             (InputMode::EditingCategoryFeeds(_), _) => {
-                " j/k:Navigate │ a:Add Feed │ d:Delete Feed │ Esc:Back ".to_string()
+                " j/k:Navigate │ a:Add Feed │ i:Import OPML │ d:Delete Feed │ U:Undo │ s:Sort │ Esc:Back ".to_string()
             }
             _ => String::new(),
         }
@@ -429,11 +778,36 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
         Style::default().fg(theme.text()).bg(theme.mantle())
     };
 
-    let status = Paragraph::new(keys).style(style);
+    let mut spans = Vec::new();
+    if app.is_loading {
+        let frame = SPINNER_FRAMES[app.spinner_frame % SPINNER_FRAMES.len()];
+        spans.push(Span::styled(
+            format!(" {} Refreshing ", frame),
+            Style::default().fg(theme.accent_primary()).bg(theme.mantle()),
+        ));
+    }
+    spans.push(Span::raw(keys));
+
+    let status = Paragraph::new(Line::from(spans)).style(style);
     f.render_widget(status, area);
 }
 
-fn draw_welcome(f: &mut Frame, _app: &App, area: Rect, theme: &dyn Theme) {
+fn draw_welcome(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
+    let footer = if let Some((done, total)) = app.import_progress {
+        let frame = SPINNER_FRAMES[app.spinner_frame % SPINNER_FRAMES.len()];
+        Span::styled(
+            format!("{} Imported {}/{}…", frame, done, total),
+            Style::default().fg(theme.accent_primary()),
+        )
+    } else if let Some(msg) = &app.message {
+        Span::styled(msg.clone(), Style::default().fg(theme.warning()))
+    } else {
+        Span::styled(
+            "OPML import asks for the file path",
+            Style::default().fg(theme.subtext()).add_modifier(Modifier::ITALIC),
+        )
+    };
+
     let welcome_text = vec![
         Line::from(""),
         Line::from(Span::styled(
@@ -461,10 +835,7 @@ fn draw_welcome(f: &mut Frame, _app: &App, area: Rect, theme: &dyn Theme) {
             Span::styled("Quit", Style::default().fg(theme.text())),
         ]),
         Line::from(""),
-        Line::from(Span::styled(
-            "OPML files are searched in ~/Downloads/",
-            Style::default().fg(theme.subtext()).add_modifier(Modifier::ITALIC),
-        )),
+        Line::from(footer),
     ];
 
     let paragraph = Paragraph::new(welcome_text).alignment(Alignment::Center).block(
@@ -484,14 +855,8 @@ fn draw_input_modal(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme, tit
     let popup_area = centered_rect(60, 20, area);
     f.render_widget(Clear, popup_area);
 
-    let input_text = &app.text_input.value;
-    let cursor_pos = app.text_input.cursor_position;
-
-    let display_text = format!(
-        "{}█{}",
-        &input_text[..cursor_pos],
-        &input_text[cursor_pos..]
-    );
+    let (before_cursor, after_cursor) = app.text_input.split_at_cursor();
+    let display_text = format!("{}█{}", before_cursor, after_cursor);
 
     let paragraph = Paragraph::new(vec![
         Line::from(""),
@@ -509,7 +874,42 @@ fn draw_input_modal(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme, tit
     f.render_widget(paragraph, popup_area);
 }
 
-fn draw_category_selector(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
+/// Path prompt for an OPML import, with the last read or parse error under it
+fn draw_opml_import_modal(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
+    let popup_area = centered_rect(60, 25, area);
+    f.render_widget(Clear, popup_area);
+
+    let (before_cursor, after_cursor) = app.text_input.split_at_cursor();
+    let status = match &app.import_error {
+        Some(error) => Line::from(Span::styled(error.clone(), Style::default().fg(theme.error()))),
+        None => Line::from(Span::styled(
+            "Enter:Import │ Esc:Cancel │ ~ is your home folder",
+            Style::default().fg(theme.subtext()),
+        )),
+    };
+
+    let paragraph = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("{}█{}", before_cursor, after_cursor),
+            Style::default().fg(theme.text()),
+        )),
+        Line::from(""),
+        status,
+    ])
+    .wrap(Wrap { trim: true })
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent_primary()))
+            .title(" Import OPML File ")
+            .title_style(Style::default().fg(theme.accent_secondary()).add_modifier(Modifier::BOLD)),
+    );
+
+    f.render_widget(paragraph, popup_area);
+}
+
+fn draw_category_selector(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme, title: &str) {
     let popup_area = centered_rect(40, 50, area);
     f.render_widget(Clear, popup_area);
 
@@ -534,7 +934,7 @@ fn draw_category_selector(f: &mut Frame, app: &App, area: Rect, theme: &dyn Them
         Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(theme.accent_primary()))
-            .title(" Select Category ")
+            .title(title.to_string())
             .title_style(Style::default().fg(theme.accent_secondary()).add_modifier(Modifier::BOLD)),
     );
 
@@ -543,6 +943,49 @@ fn draw_category_selector(f: &mut Frame, app: &App, area: Rect, theme: &dyn Them
     f.render_stateful_widget(list, popup_area, &mut state);
 }
 
+fn draw_discovered_feeds(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme) {
+    let popup_area = centered_rect(60, 50, area);
+    f.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = app
+        .discovered_feeds
+        .iter()
+        .enumerate()
+        .map(|(i, feed)| {
+            let is_selected = i == app.discovered_feed_index;
+            let style = if is_selected {
+                Style::default().fg(theme.accent_secondary()).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text())
+            };
+            let prefix = if is_selected { "▶ " } else { "  " };
+            let mut lines = vec![Line::from(Span::styled(
+                format!("{}{}", prefix, feed.title.as_deref().unwrap_or(&feed.url)),
+                style,
+            ))];
+            if feed.title.is_some() {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", feed.url),
+                    Style::default().fg(theme.subtext()),
+                )));
+            }
+            ListItem::new(lines)
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent_primary()))
+            .title(" Feeds found on this page ")
+            .title_style(Style::default().fg(theme.accent_secondary()).add_modifier(Modifier::BOLD)),
+    );
+
+    let mut state = ListState::default();
+    state.select(Some(app.discovered_feed_index));
+    f.render_stateful_widget(list, popup_area, &mut state);
+}
+
 fn draw_category_feeds_editor(f: &mut Frame, app: &App, area: Rect, theme: &dyn Theme, category: &str) {
     let popup_area = centered_rect(70, 70, area);
     f.render_widget(Clear, popup_area);
@@ -579,8 +1022,8 @@ fn draw_category_feeds_editor(f: &mut Frame, app: &App, area: Rect, theme: &dyn
         .map(|(i, feed)| {
             let is_selected = i == app.category_feed_index;
             let title = feed.title.as_deref().unwrap_or("(No title)");
-            let url = if feed.url.len() > 50 {
-                format!("{}…", &feed.url[..49])
+            let url = if feed.url.chars().count() > 50 {
+                format!("{}…", feed.url.chars().take(49).collect::<String>())
             } else {
                 feed.url.clone()
             };
@@ -592,17 +1035,61 @@ fn draw_category_feeds_editor(f: &mut Frame, app: &App, area: Rect, theme: &dyn
             };
 
             let cursor = if is_selected { "▶ " } else { "  " };
-
-            ListItem::new(vec![
+            let failing = feed.consecutive_failures >= FAILING_FEED_STREAK;
+            let unread = app.category_feed_unread.get(&feed.id).copied().unwrap_or(0);
+            let updated = feed
+                .last_fetched_at
+                .map(|t| format!("updated {}", format_relative(t)))
+                .unwrap_or_else(|| "never updated".to_string());
+
+            let mut lines = vec![
                 Line::from(vec![
                     Span::styled(cursor, Style::default().fg(theme.accent_primary())),
+                    Span::styled(if failing { "● " } else { "" }, Style::default().fg(theme.error())),
                     Span::styled(title, style),
+                    Span::styled(format!(" ({} unread)", unread), Style::default().fg(theme.subtext())),
+                    Span::styled(format!("  {}", updated), Style::default().fg(theme.overlay())),
                 ]),
                 Line::from(Span::styled(
                     format!("    {}", url),
                     Style::default().fg(theme.subtext()),
                 )),
-            ])
+            ];
+            if let Some(error) = &feed.last_error {
+                let error = if error.chars().count() > 90 {
+                    format!("{}…", error.chars().take(89).collect::<String>())
+                } else {
+                    error.clone()
+                };
+                let (prefix, color) = if failing {
+                    (format!("failed {} times in a row: ", feed.consecutive_failures), theme.error())
+                } else {
+                    (String::new(), theme.warning())
+                };
+                lines.push(Line::from(Span::styled(
+                    format!("    ⚠ {}{}", prefix, error),
+                    Style::default().fg(color),
+                )));
+            } else if app.config.feeds.allows_invalid_certs(&feed.url) {
+                lines.push(Line::from(Span::styled(
+                    "    ⚠ certificate checks disabled for this feed",
+                    Style::default().fg(theme.warning()),
+                )));
+            }
+            if is_selected && let Some(description) = &feed.description {
+                let description = description.split_whitespace().collect::<Vec<_>>().join(" ");
+                let description = if description.chars().count() > 80 {
+                    format!("{}…", description.chars().take(79).collect::<String>())
+                } else {
+                    description
+                };
+                lines.push(Line::from(Span::styled(
+                    format!("    {}", description),
+                    Style::default().fg(theme.overlay()).add_modifier(Modifier::ITALIC),
+                )));
+            }
+
+            ListItem::new(lines)
         })
         .collect();
 
@@ -610,7 +1097,12 @@ fn draw_category_feeds_editor(f: &mut Frame, app: &App, area: Rect, theme: &dyn
         Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(theme.accent_primary()))
-            .title(format!(" Feeds in '{}' ({}) ", category, app.category_feeds.len()))
+            .title(format!(
+                " Feeds in '{}' ({}) · {} ",
+                category,
+                app.category_feeds.len(),
+                app.feed_sort.label()
+            ))
             .title_style(Style::default().fg(theme.accent_secondary()).add_modifier(Modifier::BOLD)),
     );
 
@@ -655,13 +1147,22 @@ fn draw_help_overlay(f: &mut Frame, area: Rect, theme: &dyn Theme) {
         Line::from(Span::styled("Navigation", Style::default().fg(theme.accent_primary()).add_modifier(Modifier::BOLD))),
         Line::from("  h/l         Focus left/right pane"),
         Line::from("  j/k         Navigate up/down"),
+        Line::from("  Ctrl+D/U    Half page down/up (posts, article)"),
+        Line::from("  g/G         Top/bottom (posts, article)"),
         Line::from("  Enter       Select/Open item"),
+        Line::from("  1-5         Fresh / All Feeds / Starred / Later / Archive"),
+        Line::from("  6           Feed manager for the current category"),
         Line::from("  Esc         Go back / Cancel"),
+        Line::from("  g / 0       Dashboard (g from the sidebar), b toggles its banner"),
+        Line::from("  /           Search all posts (Esc in posts clears)"),
+        Line::from("  F           Filter the loaded posts by title"),
+        Line::from("  O           Open data and config folders"),
         Line::from(""),
         Line::from(Span::styled("Sidebar", Style::default().fg(theme.accent_primary()).add_modifier(Modifier::BOLD))),
         Line::from("  a / +       Add new feed (with category selection)"),
         Line::from("  n           Add new category"),
         Line::from("  e           Edit category feeds (view/delete feeds)"),
+        Line::from("  m           Move all feeds in category to another"),
         Line::from("  d           Delete selected category"),
         Line::from(""),
         Line::from(Span::styled("Posts List", Style::default().fg(theme.accent_primary()).add_modifier(Modifier::BOLD))),
@@ -670,17 +1171,34 @@ fn draw_help_overlay(f: &mut Frame, area: Rect, theme: &dyn Theme) {
         Line::from("  a           Toggle archive"),
         Line::from("  m           Toggle read/unread"),
         Line::from("  d           Delete post"),
+        Line::from("  P           Delete all non-starred posts in category"),
+        Line::from("  f           Cycle smart view filter (all/unread/today)"),
+        Line::from("  s           Cycle sort (newest/oldest/title/feed/unread)"),
+        Line::from("  J/K         Move post down/up the Read Later queue"),
         Line::from("  r           Refresh feeds"),
         Line::from("  u           Toggle show/hide read posts"),
+        Line::from("  x / X       Export current view (JSON / Markdown)"),
+        Line::from("  S           Share current view as a digest"),
         Line::from(""),
         Line::from(Span::styled("Article View", Style::default().fg(theme.accent_primary()).add_modifier(Modifier::BOLD))),
         Line::from("  j/k         Scroll content"),
         Line::from("  PgUp/PgDn   Scroll faster"),
+        Line::from("  J/K         Next/previous post"),
         Line::from("  o           Open in browser"),
+        Line::from("  s           Toggle summary / full content"),
+        Line::from("  f           Fetch the full article from its web page"),
+        Line::from("  L / Ctrl+S  Compare with the live page / save it as the copy"),
+        Line::from("  p           Play the attached audio/video (🎧)"),
+        Line::from("  w           Toggle line wrap (←/→ scroll when off)"),
+        Line::from("  A / Space   Toggle / pause auto-scroll"),
         Line::from("  y           Copy URL to clipboard"),
         Line::from(""),
         Line::from(Span::styled("General", Style::default().fg(theme.accent_primary()).add_modifier(Modifier::BOLD))),
         Line::from("  ?           Toggle this help"),
+        Line::from("  D           Copy diagnostics for bug reports"),
+        Line::from("  R           Reload config.toml"),
+        Line::from("  t           Cycle theme (saved to config.toml)"),
+        Line::from("  U           Undo the last post/feed delete"),
         Line::from("  q           Quit application"),
         Line::from(""),
         Line::from(Span::styled("Press any key to close", Style::default().fg(theme.subtext()))),
@@ -698,6 +1216,61 @@ fn draw_help_overlay(f: &mut Frame, area: Rect, theme: &dyn Theme) {
     f.render_widget(paragraph, popup_area);
 }
 
+fn draw_debug_overlay(f: &mut Frame, app: &App, metrics: &DebugMetrics, area: Rect, theme: &dyn Theme) {
+    let width = 34.min(area.width);
+    let height = 8.min(area.height);
+    let overlay_area = Rect::new(area.x + area.width - width, area.y, width, height);
+    f.render_widget(Clear, overlay_area);
+
+    let fmt_ms = |d: Option<std::time::Duration>| {
+        d.map(|d| format!("{:.1} ms", d.as_secs_f64() * 1000.0))
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    let fulltext_max = app.config.feeds.max_fulltext_fetches.max(1);
+    let fulltext_in_flight = fulltext_max.saturating_sub(app.fulltext_permits.available_permits());
+
+    let lines = vec![
+        Line::from(format!("frame   {}", fmt_ms(Some(metrics.frame_time)))),
+        Line::from(format!("fetch   {}", fmt_ms(metrics.last_fetch))),
+        Line::from(format!("query   {}", fmt_ms(metrics.last_query))),
+        Line::from(format!("posts   {}", app.posts.len())),
+        Line::from(format!("feeds   {}", app.feeds.len())),
+        Line::from(format!("fulltext {}/{}", fulltext_in_flight, fulltext_max)),
+    ];
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().fg(theme.text()))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.warning()))
+                .title(" Debug ")
+                .title_style(Style::default().fg(theme.warning()).add_modifier(Modifier::BOLD)),
+        );
+
+    f.render_widget(paragraph, overlay_area);
+}
+
+/// Starred / Read Later / Archived markers for a post, as nerd-font glyphs or
+/// plain-text tags when `ascii` is set
+fn post_badges(post: &Post, ascii: bool) -> Vec<&'static str> {
+    let mut badges = Vec::new();
+    if post.is_bookmarked {
+        badges.push(if ascii { "[FAV]" } else { "★" });
+    }
+    if post.is_read_later {
+        badges.push(if ascii { "[LATER]" } else { "󰃰" });
+    }
+    if post.is_archived {
+        badges.push(if ascii { "[ARC]" } else { "󰆧" });
+    }
+    if post.enclosure_url.is_some() {
+        badges.push(if ascii { "[AUDIO]" } else { "🎧" });
+    }
+    badges
+}
+
 fn parse_content_to_styled_lines<'a>(content: &'a str, theme: &'a dyn Theme) -> Vec<Line<'a>> {
     content
         .lines()
@@ -713,9 +1286,9 @@ fn parse_content_to_styled_lines<'a>(content: &'a str, theme: &'a dyn Theme) ->
                     Span::styled("  • ", Style::default().fg(theme.accent_primary())),
                     Span::styled(trimmed[2..].to_string(), Style::default().fg(theme.text())),
                 ])
-            } else if trimmed.starts_with("> ") {
+            } else if let Some(quote) = trimmed.strip_prefix("> ") {
                 Line::from(Span::styled(
-                    format!("│ {}", &trimmed[2..]),
+                    format!("│ {}", quote),
                     Style::default().fg(theme.subtext()).add_modifier(Modifier::ITALIC),
                 ))
             } else {